@@ -0,0 +1,49 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use itertools::Itertools;
+use rayon::prelude::*;
+use rustle::WordleDictionary::Dictionary;
+use rustle::WordleSolver::{self, PatternMatrix, SolverOptions};
+use std::hint::black_box;
+
+fn patterns(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pattern");
+    group.bench_function("get_pattern", |b| {
+        b.iter(|| WordleSolver::get_pattern(black_box("usurier"), black_box("usagers")));
+    });
+    group.bench_function("get_pattern_code", |b| {
+        b.iter(|| WordleSolver::get_pattern_code(black_box("usurier"), black_box("usagers")));
+    });
+    group.finish();
+}
+
+fn first_turn(c: &mut Criterion) {
+    let dictionary = Dictionary::lexique();
+    let words = dictionary.words(5, "t");
+    let possible_words = words.iter().map(|(word, _)| word.clone()).collect_vec();
+    let matrix = PatternMatrix::new(dictionary, 5, "t").unwrap();
+    let options = SolverOptions::default();
+
+    let mut group = c.benchmark_group("first turn, 5 letters starting with t");
+    group.sample_size(10);
+    group.bench_function("grouped get_pattern vectors", |b| {
+        b.iter(|| {
+            words
+                .par_iter()
+                .map(|(word, _)| WordleSolver::entropy_by_word(word, &possible_words))
+                .collect::<Vec<_>>()
+        });
+    });
+    group.bench_function("sorted pattern codes", |b| {
+        b.iter(|| WordleSolver::retrieve_recommended_words(dictionary, &[], 5, "t", &options));
+    });
+    group.bench_function("pattern matrix lookup", |b| {
+        b.iter(|| WordleSolver::retrieve_recommended_words_with_matrix(&matrix, &[], &options));
+    });
+    group.bench_function("pattern matrix build", |b| {
+        b.iter(|| PatternMatrix::new(dictionary, 5, "t"));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, patterns, first_turn);
+criterion_main!(benches);
@@ -0,0 +1,129 @@
+use crate::WordleDictionary::{Dictionary, Normalization};
+use crate::WordleSolver::{self, Pattern, SolverError};
+use itertools::Itertools;
+
+/// Absurdle-like game whose host never picks a hidden word: each guess gets the pattern
+/// keeping the most candidates, so the game only ends once a single candidate is guessed.
+#[derive(PartialEq, Clone, Debug)]
+pub struct AdversarialGame {
+    word_length: usize,
+    /// Normalization of the dictionary words, applied to the guesses.
+    normalization: Normalization,
+    words: Vec<String>,
+    candidates: Vec<String>,
+    steps: Vec<(String, Vec<Pattern>)>,
+}
+
+impl AdversarialGame {
+    /// Starts a game among the words of `word_length` letters starting with `first_char`,
+    /// the secret staying one of the answers, see `Dictionary::is_answer`.
+    /// # Errors
+    /// Returns `SolverError::NoAnswer` if no answer fits, the game could never be won.
+    pub fn new(
+        dictionary: &Dictionary,
+        word_length: usize,
+        first_char: &str,
+    ) -> Result<Self, SolverError> {
+        let words = dictionary
+            .words(word_length, first_char)
+            .iter()
+            .map(|(word, _)| word.clone())
+            .collect_vec();
+        let candidates = words
+            .iter()
+            .filter(|word| dictionary.is_answer(word))
+            .cloned()
+            .collect_vec();
+        if candidates.is_empty() {
+            return Err(SolverError::NoAnswer {
+                word_length,
+                first_char: first_char.to_string(),
+            });
+        }
+        Ok(Self {
+            word_length,
+            normalization: dictionary.normalization().clone(),
+            candidates,
+            words,
+            steps: Vec::new(),
+        })
+    }
+
+    #[must_use]
+    pub fn word_length(&self) -> usize {
+        self.word_length
+    }
+
+    /// Answers still consistent with every pattern given so far.
+    #[must_use]
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    /// Guessed words with the pattern the host gave them.
+    #[must_use]
+    pub fn steps(&self) -> &[(String, Vec<Pattern>)] {
+        &self.steps
+    }
+
+    #[must_use]
+    pub fn is_won(&self) -> bool {
+        self.steps
+            .last()
+            .is_some_and(|(_, pattern)| pattern.iter().all(|&pattern| pattern == Pattern::Correct))
+    }
+
+    /// Scores `word` with the pattern shared by the most candidates, keeping only these.
+    /// Ties go to the pattern revealing the least: the fewest correct letters, then the fewest
+    /// misplaced ones, then the smallest pattern code so that the host stays deterministic.
+    /// # Errors
+    /// Returns a `SolverError` if the word is not alphabetic, does not have the game length
+    /// or is not part of the game words.
+    pub fn guess(&mut self, word: &str) -> Result<Vec<Pattern>, SolverError> {
+        let word = self.normalization.normalize(word.trim());
+        if !word.chars().all(char::is_alphabetic) {
+            return Err(SolverError::NonAlphabeticGuess { word });
+        }
+        if word.chars().count() != self.word_length {
+            return Err(SolverError::WordLengthMismatch {
+                word,
+                word_length: self.word_length,
+            });
+        }
+        if self.words.binary_search(&word).is_err() {
+            return Err(SolverError::UnknownWord { word });
+        }
+        let (pattern, candidates) = self
+            .candidates
+            .iter()
+            .map(|candidate| {
+                (
+                    WordleSolver::get_pattern(&word, candidate),
+                    candidate.clone(),
+                )
+            })
+            .into_group_map()
+            .into_iter()
+            .max_by(|(a_pattern, a), (b_pattern, b)| {
+                a.len().cmp(&b.len()).then_with(|| {
+                    revealed(b_pattern).cmp(&revealed(a_pattern)).then_with(|| {
+                        WordleSolver::encode_pattern(b_pattern)
+                            .cmp(&WordleSolver::encode_pattern(a_pattern))
+                    })
+                })
+            })
+            .unwrap_or_else(|| (vec![Pattern::Incorrect; self.word_length], Vec::new()));
+        self.candidates = candidates;
+        self.steps.push((word, pattern.clone()));
+        Ok(pattern)
+    }
+}
+
+/// Correct then misplaced letters of a pattern, the most revealing patterns being the largest.
+fn revealed(pattern: &[Pattern]) -> (usize, usize) {
+    let count = |kind| pattern.iter().filter(|&&pattern| pattern == kind).count();
+    (count(Pattern::Correct), count(Pattern::Misplaced))
+}
+
+#[cfg(test)]
+mod tests;
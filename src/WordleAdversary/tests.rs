@@ -0,0 +1,67 @@
+use super::*;
+use crate::WordleFixtures::dictionary;
+
+#[test]
+fn Adversary_keeps_the_largest_bucket() {
+    let mut game = AdversarialGame::new(&dictionary(), 5, "").unwrap();
+    // blocu leaves tarie and taris all grey, crise, poele and itself on their own.
+    let pattern = game.guess("blocu").unwrap();
+    assert_eq!(pattern, vec![Pattern::Incorrect; 5]);
+    assert_eq!(game.candidates(), ["tarie", "taris"]);
+    assert!(!game.is_won());
+}
+
+#[test]
+fn Adversary_is_won_on_the_last_candidate() {
+    let mut game = AdversarialGame::new(&dictionary(), 5, "").unwrap();
+    game.guess("blocu").unwrap();
+    game.guess("tarie").unwrap();
+    assert!(game.candidates().len() <= 2);
+    while !game.is_won() {
+        let candidate = game.candidates()[0].clone();
+        game.guess(&candidate).unwrap();
+    }
+    assert_eq!(game.candidates().len(), 1);
+    assert_eq!(game.steps().last().unwrap().0, game.candidates()[0]);
+}
+
+#[test]
+fn Adversary_rejects_invalid_guesses() {
+    let mut game = AdversarialGame::new(&dictionary(), 5, "").unwrap();
+    assert_eq!(
+        game.guess("zzzzz"),
+        Err(SolverError::UnknownWord {
+            word: "zzzzz".to_string()
+        })
+    );
+    assert!(matches!(
+        game.guess("tari"),
+        Err(SolverError::WordLengthMismatch { .. })
+    ));
+    assert!(matches!(
+        game.guess("tar1e"),
+        Err(SolverError::NonAlphabeticGuess { .. })
+    ));
+    assert!(game.steps().is_empty());
+}
+
+#[test]
+fn Adversary_ties_go_to_the_least_revealing_pattern() {
+    let dictionary = Dictionary::from_words([("tarie", 1.0), ("tonus", 1.0), ("bilou", 1.0)]);
+    let mut game = AdversarialGame::new(&dictionary, 5, "").unwrap();
+    // tonus gets a correct t, bilou only a misplaced i.
+    let pattern = game.guess("tarie").unwrap();
+    assert_eq!(WordleSolver::format_pattern(&pattern), "00010");
+    assert_eq!(game.candidates(), ["bilou"]);
+}
+
+#[test]
+fn Adversary_needs_an_answer() {
+    assert_eq!(
+        AdversarialGame::new(&dictionary(), 7, ""),
+        Err(SolverError::NoAnswer {
+            word_length: 7,
+            first_char: String::new()
+        })
+    );
+}
@@ -0,0 +1,110 @@
+use crate::WordleDictionary::Dictionary;
+use crate::WordleSolver::{self, SolverError, SolverOptions};
+use itertools::Itertools;
+use serde::Serialize;
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct TurnAnalysis {
+    pub guess: String,
+    pub pattern: String,
+    pub candidates_before: usize,
+    pub candidates_after: usize,
+    /// Information the guess was expected to give, its entropy over the candidates.
+    pub expected_entropy: f32,
+    /// Information the guess actually gave, `-log2` of the probability of its pattern.
+    pub actual_bits: f32,
+    /// Guess the solver would have played at that turn, see `WordleSolver::suggested_guess`.
+    pub best_guess: String,
+    pub best_entropy: f32,
+    /// Bits gained beyond the expected ones, negative when the pattern was worse than average.
+    pub luck: f32,
+}
+
+/// Grades each guess of a finished game against the solver, turn by turn.
+/// # Errors
+/// Returns a `SolverError` if a guess is not a word of the answer length, or if the answer
+/// is not in the dictionary among the words starting with `first_char`, or is outside of its
+/// answers, see `Dictionary::restrict_answers`.
+pub fn analyse_game(
+    dictionary: &Dictionary,
+    guesses: &[String],
+    answer: &str,
+    first_char: &str,
+    options: &SolverOptions,
+) -> Result<Vec<TurnAnalysis>, SolverError> {
+    let answer = dictionary.normalize(answer.trim());
+    let word_length = answer.chars().count();
+    let words = dictionary.words(word_length, first_char);
+    if words
+        .binary_search_by(|(word, _)| word.cmp(&answer))
+        .is_err()
+        || !dictionary.is_answer(&answer)
+    {
+        return Err(SolverError::UnknownWord { word: answer });
+    }
+    let mut steps: Vec<(String, String)> = Vec::new();
+    let mut turns = Vec::new();
+    for guess in guesses {
+        let guess = dictionary.normalize(guess.trim());
+        if !guess.chars().all(char::is_alphabetic) {
+            return Err(SolverError::NonAlphabeticGuess { word: guess });
+        }
+        if guess.chars().count() != word_length {
+            return Err(SolverError::WordLengthMismatch {
+                word: guess,
+                word_length,
+            });
+        }
+        let pattern = WordleSolver::format_pattern(&WordleSolver::get_pattern(&guess, &answer));
+        let rule = WordleSolver::parse_step(&guess, &pattern, word_length)?;
+        let mut recommended = WordleSolver::retrieve_recommended_words(
+            dictionary,
+            &steps,
+            word_length,
+            first_char,
+            options,
+        )?;
+        options.scoring.sort(&mut recommended);
+        let candidates = recommended
+            .iter()
+            .filter(|we| we.is_candidate)
+            .collect_vec();
+        let remaining = candidates
+            .iter()
+            .filter(|we| rule.Is_Word_Conform(&we.word))
+            .collect_vec();
+        // Guesses missing from the dictionary are scored on the fly, over the same probabilities.
+        let expected_entropy = recommended.iter().find(|we| we.word == guess).map_or_else(
+            || {
+                let candidate_words = candidates
+                    .iter()
+                    .map(|we| (we.word.clone(), we.probability))
+                    .collect_vec();
+                WordleSolver::weighted_entropy_by_word(&guess, &candidate_words)
+            },
+            |we| we.entropy,
+        );
+        let pattern_probability = remaining.iter().map(|we| we.probability).sum::<f32>();
+        let actual_bits = -pattern_probability.log2();
+        let best = WordleSolver::suggested_guess(&recommended);
+        turns.push(TurnAnalysis {
+            pattern: pattern.clone(),
+            candidates_before: candidates.len(),
+            candidates_after: remaining.len(),
+            expected_entropy,
+            actual_bits,
+            best_guess: best.map(|we| we.word.clone()).unwrap_or_default(),
+            best_entropy: best.map_or(0.0, |we| we.entropy),
+            luck: actual_bits - expected_entropy,
+            guess: guess.clone(),
+        });
+        if guess == answer {
+            break;
+        }
+        steps.push((guess, pattern));
+    }
+    Ok(turns)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,133 @@
+#![allow(clippy::float_cmp)]
+use super::*;
+use crate::WordleDictionary::{AnswerFilter, DictionaryFormat};
+use crate::WordleFixtures::{weighted_dictionary, WORDS};
+use crate::WordleSolver::Prior;
+
+fn guesses(words: &[&str]) -> Vec<String> {
+    words.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn Analysis_stops_at_the_answer() {
+    let dictionary = weighted_dictionary();
+    let turns = analyse_game(
+        &dictionary,
+        &guesses(&["crise", "taris", "poele"]),
+        "taris",
+        "",
+        &SolverOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(turns.len(), 2);
+    assert_eq!(turns[0].guess, "crise");
+    assert_eq!(turns[0].pattern, "01110");
+    assert_eq!(turns[0].candidates_before, 5);
+    assert_eq!(turns[0].candidates_after, 1);
+    // Every candidate gives crise a different pattern.
+    assert!((turns[0].expected_entropy - 5f32.log2()).abs() < 1e-5);
+    assert!((turns[0].actual_bits - 5f32.log2()).abs() < 1e-5);
+    assert!(turns[0].luck.abs() < 1e-5);
+    assert_eq!(turns[1].guess, "taris");
+    assert_eq!(turns[1].pattern, "22222");
+    assert_eq!(turns[1].candidates_before, 1);
+    assert_eq!(turns[1].best_guess, "taris");
+    assert_eq!(turns[1].actual_bits, 0.0);
+}
+
+#[test]
+fn Luck_compares_actual_and_expected_bits() {
+    let dictionary = weighted_dictionary();
+    // taris splits the candidates in {tarie}, {taris}, {crise} and {poele, blocu}.
+    let expected = 0.6 * 5f32.log2() + 0.4 * 2.5f32.log2();
+    let lucky = analyse_game(
+        &dictionary,
+        &guesses(&["taris"]),
+        "tarie",
+        "",
+        &SolverOptions::default(),
+    )
+    .unwrap();
+    assert!((lucky[0].expected_entropy - expected).abs() < 1e-5);
+    assert!((lucky[0].luck - (5f32.log2() - expected)).abs() < 1e-5);
+    assert!(lucky[0].luck > 0.0);
+    let unlucky = analyse_game(
+        &dictionary,
+        &guesses(&["taris"]),
+        "poele",
+        "",
+        &SolverOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(unlucky[0].candidates_after, 2);
+    assert!((unlucky[0].luck - (2.5f32.log2() - expected)).abs() < 1e-5);
+    assert!(unlucky[0].luck < 0.0);
+}
+
+#[test]
+fn Analysis_rejects_invalid_games() {
+    let dictionary = weighted_dictionary();
+    let options = SolverOptions::default();
+    assert_eq!(
+        analyse_game(&dictionary, &guesses(&["tarie"]), "bloca", "", &options),
+        Err(SolverError::UnknownWord {
+            word: "bloca".to_string()
+        })
+    );
+    assert_eq!(
+        analyse_game(&dictionary, &guesses(&["tari"]), "tarie", "", &options),
+        Err(SolverError::WordLengthMismatch {
+            word: "tari".to_string(),
+            word_length: 5
+        })
+    );
+}
+
+#[test]
+fn Analysis_rejects_answers_outside_of_the_pool() {
+    let content = "1_ortho;3_lemme;10_freqlivres\ntarie;tarir;1\ntarir;tarir;1\n";
+    let mut dictionary = Dictionary::from_csv(content, &DictionaryFormat::lexique()).unwrap();
+    dictionary
+        .restrict_answers(&AnswerFilter {
+            lemmas_only: true,
+            ..AnswerFilter::default()
+        })
+        .unwrap();
+    let options = SolverOptions::default();
+    assert_eq!(
+        analyse_game(&dictionary, &guesses(&["tarir"]), "tarie", "", &options),
+        Err(SolverError::UnknownWord {
+            word: "tarie".to_string()
+        })
+    );
+    assert!(analyse_game(&dictionary, &guesses(&["tarie"]), "tarir", "", &options).is_ok());
+}
+
+#[test]
+fn Guesses_outside_of_the_dictionary_use_the_prior() {
+    let dictionary = weighted_dictionary();
+    let options = SolverOptions {
+        prior: Prior::DEFAULT_SIGMOID,
+        ..SolverOptions::default()
+    };
+    let turns = analyse_game(&dictionary, &guesses(&["taria"]), "tarie", "", &options).unwrap();
+    let probabilities = WordleSolver::retrieve_recommended_words(&dictionary, &[], 5, "", &options)
+        .unwrap()
+        .into_iter()
+        .map(|we| (we.word, we.probability))
+        .collect::<std::collections::HashMap<_, _>>();
+    // taria splits the candidates in {tarie, taris}, {crise} and {poele, blocu}.
+    let expected = [
+        probabilities["tarie"] + probabilities["taris"],
+        probabilities["crise"],
+        probabilities["poele"] + probabilities["blocu"],
+    ]
+    .iter()
+    .map(|probability| -probability * probability.log2())
+    .sum::<f32>();
+    assert!((turns[0].expected_entropy - expected).abs() < 1e-5);
+    let words = WORDS.map(ToString::to_string);
+    assert!(
+        (turns[0].expected_entropy - WordleSolver::entropy_by_word("taria", &words)).abs() > 0.1
+    );
+}
@@ -0,0 +1,140 @@
+use crate::WordleDictionary::Dictionary;
+use crate::WordleGame::DEFAULT_MIN_FREQUENCY;
+use chrono::NaiveDate;
+use itertools::Itertools;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Number of days a word of the day cannot come back.
+pub const DEFAULT_WINDOW: usize = 30;
+pub const DEFAULT_START: NaiveDate = match NaiveDate::from_ymd_opt(2024, 1, 1) {
+    Some(date) => date,
+    None => panic!("2024-01-01 is a valid date"),
+};
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct DailyOptions {
+    pub word_length: usize,
+    /// First letter of every word of the day, any letter if empty.
+    pub first_char: String,
+    /// Minimum `freqlivres` frequency of the words of the day.
+    pub min_frequency: f32,
+    /// Seed of the sequence, two challenges with different seeds get different words.
+    pub seed: u64,
+    /// A word is never picked again within this number of days.
+    pub window: usize,
+    /// First day of the sequence, the words of the following days depend on it.
+    pub start: NaiveDate,
+}
+
+impl DailyOptions {
+    #[must_use]
+    pub fn new(word_length: usize, first_char: &str) -> Self {
+        Self {
+            word_length,
+            first_char: first_char.to_string(),
+            min_frequency: DEFAULT_MIN_FREQUENCY,
+            seed: 0,
+            window: DEFAULT_WINDOW,
+            start: DEFAULT_START,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum DailyError {
+    BeforeStart {
+        date: NaiveDate,
+        start: NaiveDate,
+    },
+    /// There must be more words than days in the window for every day to get a new one.
+    NotEnoughWords {
+        available: usize,
+        window: usize,
+    },
+}
+
+impl fmt::Display for DailyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BeforeStart { date, start } => {
+                write!(f, "{date} is before the first day of the sequence, {start}")
+            }
+            Self::NotEnoughWords { available, window } => write!(
+                f,
+                "Only {available} words fit the options, more than {window} are needed not to repeat one within {window} days"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DailyError {}
+
+/// Words of the day from `options.start` up to `date` included, oldest first.
+/// Each day draws, with a `ChaCha8Rng` seeded by `options.seed`, one of the answers of
+/// the options frequent enough that was not drawn in the previous `options.window` days.
+/// The generator and the draw are fixed so that the sequence does not change with the
+/// version of `rand`, and each day only takes a draw to replay.
+/// # Errors
+/// Returns a `DailyError` if `date` is before the start of the sequence or if there are not
+/// more words than days in the window.
+pub fn daily_words(
+    dictionary: &Dictionary,
+    date: NaiveDate,
+    options: &DailyOptions,
+) -> Result<Vec<(NaiveDate, String)>, DailyError> {
+    let start = options.start;
+    let days = usize::try_from((date - start).num_days())
+        .map_err(|_| DailyError::BeforeStart { date, start })?;
+    let mut available = dictionary
+        .words(options.word_length, &options.first_char)
+        .iter()
+        .filter(|(word, freq)| *freq >= options.min_frequency && dictionary.is_answer(word))
+        .map(|(word, _)| word.as_str())
+        .collect_vec();
+    if available.len() <= options.window {
+        return Err(DailyError::NotEnoughWords {
+            available: available.len(),
+            window: options.window,
+        });
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(options.seed);
+    // Words drawn in the window, given back to the available ones once it has passed.
+    let mut recent: VecDeque<&str> = VecDeque::with_capacity(options.window + 1);
+    let mut history = Vec::with_capacity(days + 1);
+    for day in start.iter_days().take(days + 1) {
+        // The window is shorter than the word list, there is always a word available.
+        let word = available.swap_remove(draw(&mut rng, available.len()));
+        recent.push_back(word);
+        if recent.len() > options.window {
+            available.extend(recent.pop_front());
+        }
+        history.push((day, word.to_string()));
+    }
+    Ok(history)
+}
+
+/// Index below `len` from the next 64 bits of `rng`, scaled rather than rejected.
+#[allow(clippy::cast_possible_truncation)]
+fn draw(rng: &mut ChaCha8Rng, len: usize) -> usize {
+    ((u128::from(rng.next_u64()) * len as u128) >> 64) as usize
+}
+
+/// The word of the day for `date`, see `daily_words`.
+/// # Errors
+/// Returns a `DailyError` if `date` is before the start of the sequence or if there are not
+/// more words than days in the window.
+pub fn word_of_the_day(
+    dictionary: &Dictionary,
+    date: NaiveDate,
+    options: &DailyOptions,
+) -> Result<String, DailyError> {
+    let mut history = daily_words(dictionary, date, options)?;
+    Ok(history.pop().map(|(_, word)| word).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,84 @@
+use super::*;
+use crate::WordleFixtures;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn Word_of_the_day_is_reproducible() {
+    let options = DailyOptions::new(5, "t");
+    let today = date(2024, 6, 1);
+    let word = word_of_the_day(Dictionary::lexique(), today, &options).unwrap();
+    assert_eq!(
+        word,
+        word_of_the_day(Dictionary::lexique(), today, &options).unwrap()
+    );
+    assert!(word.starts_with('t'));
+    assert!(Dictionary::lexique()
+        .words(5, "t")
+        .iter()
+        .any(|(known, freq)| *known == word && *freq >= options.min_frequency));
+    let other_seed = DailyOptions {
+        seed: 1,
+        ..options.clone()
+    };
+    let history = daily_words(Dictionary::lexique(), today, &options).unwrap();
+    let other_history = daily_words(Dictionary::lexique(), today, &other_seed).unwrap();
+    assert_ne!(history, other_history);
+}
+
+#[test]
+fn Words_do_not_repeat_within_the_window() {
+    let options = DailyOptions {
+        window: 50,
+        ..DailyOptions::new(5, "t")
+    };
+    let history = daily_words(Dictionary::lexique(), date(2024, 12, 31), &options).unwrap();
+    assert_eq!(history.len(), 366);
+    assert_eq!(history[0].0, options.start);
+    for window in history.windows(options.window + 1) {
+        assert!(window.iter().map(|(_, word)| word).all_unique());
+    }
+}
+
+#[test]
+fn Daily_errors() {
+    let options = DailyOptions::new(5, "t");
+    assert_eq!(
+        word_of_the_day(Dictionary::lexique(), date(2023, 12, 31), &options),
+        Err(DailyError::BeforeStart {
+            date: date(2023, 12, 31),
+            start: options.start
+        })
+    );
+    let dictionary = Dictionary::from_words([("tarie", 1.0), ("taris", 1.0)]);
+    assert_eq!(
+        word_of_the_day(&dictionary, date(2024, 1, 2), &options),
+        Err(DailyError::NotEnoughWords {
+            available: 2,
+            window: DEFAULT_WINDOW
+        })
+    );
+    let short_window = DailyOptions {
+        window: 1,
+        ..options
+    };
+    let history = daily_words(&dictionary, date(2024, 1, 10), &short_window).unwrap();
+    assert!(history.windows(2).all(|pair| pair[0].1 != pair[1].1));
+}
+
+#[test]
+fn Daily_sequence_is_pinned() {
+    // The draws do not depend on the version of rand, a change here changes every past day.
+    let options = DailyOptions {
+        min_frequency: 0.0,
+        window: 2,
+        ..DailyOptions::new(5, "")
+    };
+    let history = daily_words(&WordleFixtures::dictionary(), date(2024, 1, 8), &options).unwrap();
+    assert_eq!(
+        history.into_iter().map(|(_, word)| word).collect_vec(),
+        ["tarie", "crise", "poele", "blocu", "crise", "taris", "blocu", "crise"]
+    );
+}
@@ -0,0 +1,527 @@
+use deunicode::deunicode;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// The 26 letters without accents, those of the English words.
+pub const LATIN_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+/// Letters of the French words, the accented ones only remain when they are not folded.
+pub const FRENCH_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyzàâæçéèêëîïôœùûüÿ";
+/// Columns of a complete Lexique 3.81 file the embedded one should keep, see `lexique_columns`.
+pub const LEXIQUE_COLUMNS: [&str; 6] = [
+    "1_ortho",
+    "3_lemme",
+    "4_cgram",
+    "5_genre",
+    "6_nombre",
+    "10_freqlivres",
+];
+const LEXIQUE: &str = include_str!("Lexique381.csv");
+static LEXIQUE_DICTIONARY: OnceLock<Dictionary> = OnceLock::new();
+
+/// How words are normalized before being compared, applied alike to the dictionary, the guesses
+/// and the letters of the patterns.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub enum Normalization {
+    /// Folds accents and ligatures to ASCII with `deunicode`, as Sutom does: é is an e and
+    /// œ is oe. Words folding to the same form are merged, their frequencies summed up.
+    #[default]
+    Fold,
+    /// Keeps the words as written, é and e are different letters.
+    Keep,
+    /// Replaces each listed letter by its text, e.g. œ by oe and ß by ss, and keeps the others.
+    Custom(Vec<(char, String)>),
+}
+
+impl Normalization {
+    /// Lowercases the word, then folds or replaces its letters.
+    #[must_use]
+    pub fn normalize(&self, word: &str) -> String {
+        let word = word.to_lowercase();
+        match self {
+            Self::Fold => deunicode(&word),
+            Self::Keep => word,
+            Self::Custom(mapping) => word
+                .chars()
+                .map(|c| {
+                    mapping
+                        .iter()
+                        .find(|(from, _)| *from == c)
+                        .map_or_else(|| c.to_string(), |(_, to)| to.clone())
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fold => write!(f, "fold"),
+            Self::Keep => write!(f, "keep"),
+            Self::Custom(mapping) => write!(
+                f,
+                "{}",
+                mapping
+                    .iter()
+                    .map(|(from, to)| format!("{from}={to}"))
+                    .join(",")
+            ),
+        }
+    }
+}
+
+impl FromStr for Normalization {
+    type Err = String;
+
+    /// `fold`, `keep` or a custom mapping such as `œ=oe,ß=ss`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "fold" => Ok(Self::Fold),
+            "keep" => Ok(Self::Keep),
+            mapping => mapping
+                .split(',')
+                .map(|replacement| {
+                    let (from, to) = replacement.split_once('=').ok_or_else(|| {
+                        format!("Unknown normalization '{s}', expected fold, keep or a mapping like œ=oe,ß=ss")
+                    })?;
+                    let from = from
+                        .trim()
+                        .chars()
+                        .exactly_one()
+                        .map_err(|_| format!("'{from}' should be a single letter"))?;
+                    Ok((from, to.trim().to_string()))
+                })
+                .collect::<Result<_, _>>()
+                .map(Self::Custom),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct DictionaryFormat {
+    pub separator: char,
+    pub word_column: String,
+    /// Words are all given the same frequency when there is no frequency column.
+    pub frequency_column: Option<String>,
+    /// Letters the words may use once normalized, words with other letters are skipped.
+    /// Any letter is accepted when `None`.
+    pub alphabet: Option<String>,
+    pub normalization: Normalization,
+    /// Grammatical columns of the words, see `Lexeme`, each one read when the header has it.
+    pub lemma_column: Option<String>,
+    pub category_column: Option<String>,
+    pub gender_column: Option<String>,
+    pub number_column: Option<String>,
+}
+
+impl DictionaryFormat {
+    #[must_use]
+    pub fn new(separator: char, word_column: &str, frequency_column: Option<&str>) -> Self {
+        Self {
+            separator,
+            word_column: word_column.to_string(),
+            frequency_column: frequency_column.map(str::to_string),
+            alphabet: None,
+            normalization: Normalization::Fold,
+            lemma_column: None,
+            category_column: None,
+            gender_column: None,
+            number_column: None,
+        }
+    }
+
+    /// Columns of Lexique 3.81. The embedded file only keeps the words and their frequencies
+    /// until it is regenerated with `lexique_columns`, the grammatical columns are read from
+    /// a complete Lexique given as the dictionary meanwhile.
+    #[must_use]
+    pub fn lexique() -> Self {
+        Self {
+            alphabet: Some(FRENCH_ALPHABET.to_string()),
+            lemma_column: Some("3_lemme".to_string()),
+            category_column: Some("4_cgram".to_string()),
+            gender_column: Some("5_genre".to_string()),
+            number_column: Some("6_nombre".to_string()),
+            ..Self::new(';', "1_ortho", Some("10_freqlivres"))
+        }
+    }
+}
+
+impl Default for DictionaryFormat {
+    fn default() -> Self {
+        Self::lexique()
+    }
+}
+
+/// One of the grammatical readings of a word, as Lexique gives them: the same spelling may be
+/// a noun and a conjugated verb.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct Lexeme {
+    /// Normalized like the words, a word is a lemma when it is its own lemma.
+    pub lemma: String,
+    /// Grammatical category (`cgram`), e.g. NOM, ADJ or VER.
+    pub category: String,
+    /// `m` or `f`, empty when it does not apply.
+    pub gender: String,
+    /// `s` or `p`, empty when it does not apply.
+    pub number: String,
+}
+
+/// Which words may be the answer, every word remaining a valid guess.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct AnswerFilter {
+    /// Grammatical categories an answer may have, e.g. NOM and ADJ, any when empty.
+    pub categories: Vec<String>,
+    /// Only words that are their own lemma: no conjugated verb, no plural or feminine form.
+    pub lemmas_only: bool,
+    /// No plural form.
+    pub singular_only: bool,
+}
+
+impl AnswerFilter {
+    /// Whether every word may be the answer.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether one of the readings of `word` fits every criterion.
+    #[must_use]
+    pub fn accepts(&self, word: &str, lexemes: &[Lexeme]) -> bool {
+        self.is_empty()
+            || lexemes.iter().any(|lexeme| {
+                (self.categories.is_empty()
+                    || self
+                        .categories
+                        .iter()
+                        .any(|category| category.eq_ignore_ascii_case(&lexeme.category)))
+                    && (!self.lemmas_only || lexeme.lemma == word)
+                    && (!self.singular_only || lexeme.number != "p")
+            })
+    }
+}
+
+#[derive(Debug)]
+pub enum DictionaryError {
+    Io(std::io::Error),
+    MissingColumn(String),
+    /// Answers cannot be filtered by grammar when the word list has no grammatical columns.
+    NoGrammar,
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read the dictionary: {error}"),
+            Self::MissingColumn(column) => {
+                write!(f, "Column '{column}' is missing from the dictionary header")
+            }
+            Self::NoGrammar => write!(
+                f,
+                "The dictionary has no grammatical columns to restrict the answers with"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {}
+
+impl From<std::io::Error> for DictionaryError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Word list the solver picks its guesses and candidates from.
+/// Words are normalized, folded to ASCII by default, and indexed by length then sorted,
+/// so that every length and first letter slice is available without reparsing the source.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Dictionary {
+    words_by_length: HashMap<usize, Vec<(String, f32)>>,
+    normalization: Normalization,
+    /// Grammatical readings of the words, when the word list has them.
+    lexemes: HashMap<String, Vec<Lexeme>>,
+    /// Words that may be the answer, every word when `None`.
+    answers: Option<HashSet<String>>,
+}
+
+impl Dictionary {
+    /// The French Lexique 3.81 word list embedded in the binary, parsed on first use
+    /// and shared by every caller afterwards.
+    /// # Panics
+    /// Panics if the embedded file lost its header, which the tests guard against.
+    pub fn lexique() -> &'static Self {
+        LEXIQUE_DICTIONARY.get_or_init(|| {
+            Self::from_csv(LEXIQUE, &DictionaryFormat::lexique())
+                .expect("embedded Lexique381.csv has a 1_ortho;10_freqlivres header")
+        })
+    }
+
+    /// The embedded Lexique 3.81 read with another format, e.g. keeping its accents.
+    /// # Errors
+    /// Returns `DictionaryError::MissingColumn` if the format columns are not Lexique ones.
+    pub fn lexique_with(format: &DictionaryFormat) -> Result<Self, DictionaryError> {
+        Self::from_csv(LEXIQUE, format)
+    }
+
+    /// # Errors
+    /// Returns a `DictionaryError` if the file cannot be read or lacks one of the format columns.
+    pub fn from_path(
+        path: impl AsRef<Path>,
+        format: &DictionaryFormat,
+    ) -> Result<Self, DictionaryError> {
+        Self::from_csv(&std::fs::read_to_string(path)?, format)
+    }
+
+    /// Parses a delimited text whose first line holds the column names.
+    /// Lines with a missing word or an unparsable, infinite or negative frequency are skipped.
+    /// # Errors
+    /// Returns `DictionaryError::MissingColumn` if the header lacks one of the format columns.
+    pub fn from_csv(content: &str, format: &DictionaryFormat) -> Result<Self, DictionaryError> {
+        let mut lines = content.lines();
+        let header = lines
+            .next()
+            .unwrap_or_default()
+            .split(format.separator)
+            .map(str::trim)
+            .collect_vec();
+        let column_index = |column: &str| {
+            header
+                .iter()
+                .position(|name| *name == column)
+                .ok_or_else(|| DictionaryError::MissingColumn(column.to_string()))
+        };
+        let word_index = column_index(&format.word_column)?;
+        let frequency_index = format
+            .frequency_column
+            .as_deref()
+            .map(column_index)
+            .transpose()?;
+        let optional_index = |column: &Option<String>| {
+            column
+                .as_deref()
+                .and_then(|column| header.iter().position(|name| *name == column))
+        };
+        let grammar_indices = [
+            optional_index(&format.lemma_column),
+            optional_index(&format.category_column),
+            optional_index(&format.gender_column),
+            optional_index(&format.number_column),
+        ];
+        let has_grammar = grammar_indices.iter().any(Option::is_some);
+
+        Ok(Self::from_entries(
+            lines.filter_map(|line| {
+                let fields = line.split(format.separator).collect_vec();
+                let word = fields.get(word_index)?.trim();
+                let frequency = match frequency_index {
+                    Some(index) => fields
+                        .get(index)?
+                        .trim()
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|frequency| frequency.is_finite() && *frequency >= 0.0)?,
+                    None => 1.0,
+                };
+                let [lemma, category, gender, number] = grammar_indices.map(|index| {
+                    index
+                        .and_then(|index| fields.get(index))
+                        .map_or_else(String::new, |field| field.trim().to_string())
+                });
+                let lexeme = has_grammar.then_some(Lexeme {
+                    lemma,
+                    category,
+                    gender,
+                    number,
+                });
+                Some((word, frequency, lexeme))
+            }),
+            format.alphabet.as_deref(),
+            &format.normalization,
+        ))
+    }
+
+    /// Builds a dictionary from words already in memory, e.g. an official answer list.
+    pub fn from_words<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = (S, f32)>,
+        S: Into<String>,
+    {
+        Self::from_entries(
+            words.into_iter().map(|(word, freq)| (word, freq, None)),
+            None,
+            &Normalization::Fold,
+        )
+    }
+
+    fn from_entries<I, S>(words: I, alphabet: Option<&str>, normalization: &Normalization) -> Self
+    where
+        I: IntoIterator<Item = (S, f32, Option<Lexeme>)>,
+        S: Into<String>,
+    {
+        let mut frequencies: HashMap<String, f32> = HashMap::new();
+        let mut lexemes: HashMap<String, Vec<Lexeme>> = HashMap::new();
+        for (word, freq, lexeme) in words {
+            let word: String = word.into();
+            let Some((word, freq)) = parse_entry(&word, freq, alphabet, normalization) else {
+                continue;
+            };
+            if let Some(lexeme) = lexeme {
+                let lexeme = Lexeme {
+                    lemma: normalization.normalize(&lexeme.lemma),
+                    ..lexeme
+                };
+                let readings = lexemes.entry(word.clone()).or_default();
+                if !readings.contains(&lexeme) {
+                    readings.push(lexeme);
+                }
+            }
+            *frequencies.entry(word).or_default() += freq;
+        }
+        let mut words_by_length = frequencies
+            .into_iter()
+            .into_group_map_by(|(word, _)| word.chars().count());
+        for words in words_by_length.values_mut() {
+            words.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+        Self {
+            words_by_length,
+            normalization: normalization.clone(),
+            lexemes,
+            answers: None,
+        }
+    }
+
+    /// Whether the word list has grammatical columns, which `restrict_answers` needs.
+    #[must_use]
+    pub fn has_grammar(&self) -> bool {
+        !self.lexemes.is_empty()
+    }
+
+    /// Grammatical readings of `word`, empty when the word list has no grammatical columns.
+    #[must_use]
+    pub fn lexemes(&self, word: &str) -> &[Lexeme] {
+        self.lexemes.get(word).map_or(&[], Vec::as_slice)
+    }
+
+    /// Restricts the words that may be the answer to the ones `filter` accepts, every word
+    /// remaining a valid guess. An empty filter makes every word an answer again.
+    /// # Errors
+    /// Returns `DictionaryError::NoGrammar` if the filter is not empty and the word list has
+    /// no grammatical columns.
+    pub fn restrict_answers(&mut self, filter: &AnswerFilter) -> Result<(), DictionaryError> {
+        if filter.is_empty() {
+            self.answers = None;
+            return Ok(());
+        }
+        if !self.has_grammar() {
+            return Err(DictionaryError::NoGrammar);
+        }
+        self.answers = Some(
+            self.words_by_length
+                .values()
+                .flatten()
+                .map(|(word, _)| word)
+                .filter(|word| filter.accepts(word, self.lexemes(word)))
+                .cloned()
+                .collect(),
+        );
+        Ok(())
+    }
+
+    /// Whether `word` may be the answer, see `restrict_answers`.
+    #[must_use]
+    pub fn is_answer(&self, word: &str) -> bool {
+        self.answers
+            .as_ref()
+            .is_none_or(|answers| answers.contains(word))
+    }
+
+    #[must_use]
+    pub fn normalization(&self) -> &Normalization {
+        &self.normalization
+    }
+
+    /// A guess written as the words of the dictionary, see `Normalization`.
+    #[must_use]
+    pub fn normalize(&self, word: &str) -> String {
+        self.normalization.normalize(word)
+    }
+
+    /// Lengths of the words of the dictionary, shortest first.
+    #[must_use]
+    pub fn word_lengths(&self) -> Vec<usize> {
+        self.words_by_length.keys().copied().sorted().collect()
+    }
+
+    /// Words of `word_length` letters starting with `first_char` (any if empty),
+    /// with the frequencies of words folding to the same form summed up.
+    #[must_use]
+    pub fn words(&self, word_length: usize, first_char: &str) -> &[(String, f32)] {
+        let Some(words) = self.words_by_length.get(&word_length) else {
+            return &[];
+        };
+        let Some(first) = self.normalize(first_char).chars().next() else {
+            return words;
+        };
+        let start = words.partition_point(|(word, _)| word.chars().next() < Some(first));
+        let end = words.partition_point(|(word, _)| word.chars().next() <= Some(first));
+        &words[start..end]
+    }
+}
+
+/// The `LEXIQUE_COLUMNS` of a complete Lexique 3.81 file whose columns are separated by
+/// `separator`, as a `;` separated text to embed as `Lexique381.csv`.
+/// # Errors
+/// Returns `DictionaryError::MissingColumn` if the header lacks one of the columns.
+pub fn lexique_columns(content: &str, separator: char) -> Result<String, DictionaryError> {
+    let mut lines = content.lines();
+    let header = lines
+        .next()
+        .unwrap_or_default()
+        .split(separator)
+        .map(str::trim)
+        .collect_vec();
+    let indices = LEXIQUE_COLUMNS
+        .iter()
+        .map(|&column| {
+            header
+                .iter()
+                .position(|name| *name == column)
+                .ok_or_else(|| DictionaryError::MissingColumn(column.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(std::iter::once(LEXIQUE_COLUMNS.join(";"))
+        .chain(lines.filter(|line| !line.trim().is_empty()).map(|line| {
+            let fields = line.split(separator).collect_vec();
+            indices
+                .iter()
+                .map(|&index| fields.get(index).map_or("", |field| field.trim()))
+                .join(";")
+        }))
+        .map(|line| line + "\n")
+        .collect())
+}
+
+fn parse_entry(
+    word: &str,
+    freq: f32,
+    alphabet: Option<&str>,
+    normalization: &Normalization,
+) -> Option<(String, f32)> {
+    if word.is_empty() || !word.chars().all(char::is_alphabetic) {
+        return None;
+    }
+    let decoded_word = normalization.normalize(word);
+    let in_alphabet = |c: char| alphabet.map_or(c.is_alphabetic(), |letters| letters.contains(c));
+    if !decoded_word.chars().all(in_alphabet) {
+        return None;
+    }
+    Some((decoded_word, freq))
+}
+
+#[cfg(test)]
+mod tests;
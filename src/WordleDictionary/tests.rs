@@ -0,0 +1,308 @@
+#![allow(clippy::float_cmp)]
+use super::*;
+use itertools::Itertools;
+#[test]
+fn Parse_Wordle_entity() {
+    let parsed = parse_entry("coucou", 1.32, None, &Normalization::Fold);
+    assert_eq!(parsed, Some(("coucou".to_string(), 1.32)));
+}
+
+#[test]
+fn Parse_Wordle_entity_special_char() {
+    let parsed = parse_entry("père", 1.32, None, &Normalization::Fold);
+    assert_eq!(parsed, Some(("pere".to_string(), 1.32)));
+}
+
+#[test]
+fn Parse_Wordle_entity_space() {
+    let parsed = parse_entry("à priori", 1.32, None, &Normalization::Fold);
+    assert_eq!(parsed, None);
+}
+
+#[test]
+fn Lexique_header() {
+    let dictionary = Dictionary::lexique();
+    assert!(dictionary
+        .words(5, "t")
+        .iter()
+        .any(|(word, _)| word == "tarie"));
+}
+
+#[test]
+fn From_csv_custom_columns() {
+    let content = "freq,lemma,word\n12.5,être,été\n3,x,tarie\nnot a number,y,tarot\n";
+    let dictionary =
+        Dictionary::from_csv(content, &DictionaryFormat::new(',', "word", Some("freq"))).unwrap();
+    assert_eq!(dictionary.words(5, ""), [("tarie".to_string(), 3.0)]);
+    assert_eq!(dictionary.words(3, ""), [("ete".to_string(), 12.5)]);
+}
+
+#[test]
+fn From_csv_skips_frequencies_out_of_range() {
+    let content = "word;freq\ntarie;NaN\ntarot;inf\ntaris;-2\ntabac;0\n";
+    let dictionary =
+        Dictionary::from_csv(content, &DictionaryFormat::new(';', "word", Some("freq"))).unwrap();
+    assert_eq!(dictionary.words(5, ""), [("tabac".to_string(), 0.0)]);
+}
+
+#[test]
+fn From_csv_without_frequency() {
+    let dictionary = Dictionary::from_csv(
+        "word\ntarie\ntarot\n",
+        &DictionaryFormat::new(';', "word", None),
+    )
+    .unwrap();
+    assert_eq!(dictionary.words(5, "").len(), 2);
+    assert!(dictionary.words(5, "").iter().all(|(_, freq)| *freq == 1.0));
+}
+
+#[test]
+fn From_csv_missing_column() {
+    let result = Dictionary::from_csv("word;freq\ntarie;1\n", &DictionaryFormat::lexique());
+    assert!(matches!(result, Err(DictionaryError::MissingColumn(column)) if column == "1_ortho"));
+}
+
+#[test]
+fn From_words_merges_folded_words() {
+    let dictionary = Dictionary::from_words([("pêche", 2.0), ("péché", 1.5), ("poche", 1.0)]);
+    assert_eq!(
+        dictionary.words(5, "p"),
+        [("peche".to_string(), 3.5), ("poche".to_string(), 1.0)]
+    );
+}
+
+#[test]
+fn Words_by_length_and_first_char() {
+    let dictionary = Dictionary::from_words([
+        ("tarie", 1.0),
+        ("abats", 1.0),
+        ("tapis", 1.0),
+        ("zebre", 1.0),
+        ("tas", 1.0),
+        ("cœur", 1.0),
+    ]);
+    assert_eq!(
+        dictionary
+            .words(5, "t")
+            .iter()
+            .map(|(word, _)| word)
+            .collect_vec(),
+        ["tapis", "tarie"]
+    );
+    assert_eq!(dictionary.words(5, "").len(), 5);
+    assert_eq!(dictionary.words(5, "b"), []);
+    assert_eq!(dictionary.words(4, ""), []);
+    assert_eq!(dictionary.words(3, "t").len(), 1);
+}
+
+#[test]
+fn Lexique_is_shared() {
+    assert!(std::ptr::eq(Dictionary::lexique(), Dictionary::lexique()));
+}
+
+#[test]
+fn Alphabet_filters_words() {
+    let format = DictionaryFormat {
+        alphabet: Some("abc".to_string()),
+        ..DictionaryFormat::new(';', "word", None)
+    };
+    let dictionary = Dictionary::from_csv("word\nabba\nabcd\ncâba\n", &format).unwrap();
+    assert_eq!(
+        dictionary.words(4, ""),
+        [("abba".to_string(), 1.0), ("caba".to_string(), 1.0)]
+    );
+}
+
+#[test]
+fn Normalize_ligatures_and_cedillas() {
+    let custom: Normalization = "œ=oe, ß=ss".parse().unwrap();
+    assert_eq!(
+        custom,
+        Normalization::Custom(vec![('œ', "oe".to_string()), ('ß', "ss".to_string())])
+    );
+    for (word, fold, keep, mapped) in [
+        ("cœur", "coeur", "cœur", "coeur"),
+        ("garçon", "garcon", "garçon", "garçon"),
+        ("straße", "strasse", "straße", "strasse"),
+        ("nævus", "naevus", "nævus", "nævus"),
+        ("CŒUR", "coeur", "cœur", "coeur"),
+    ] {
+        assert_eq!(Normalization::Fold.normalize(word), fold);
+        assert_eq!(Normalization::Keep.normalize(word), keep);
+        assert_eq!(custom.normalize(word), mapped);
+    }
+}
+
+#[test]
+fn Normalization_names() {
+    for name in ["fold", "keep", "œ=oe,ß=ss"] {
+        assert_eq!(name.parse::<Normalization>().unwrap().to_string(), name);
+    }
+    assert!("oe".parse::<Normalization>().is_err());
+    assert!("oe=o".parse::<Normalization>().is_err());
+}
+
+#[test]
+fn Kept_accents_are_not_merged() {
+    let content = "1_ortho;10_freqlivres\ngarçon;2\ngarcon;1\ncœur;3\nsœur;1\n";
+    let folded = Dictionary::from_csv(content, &DictionaryFormat::lexique()).unwrap();
+    assert_eq!(folded.words(6, "g"), [("garcon".to_string(), 3.0)]);
+    assert_eq!(
+        folded.words(5, ""),
+        [("coeur".to_string(), 3.0), ("soeur".to_string(), 1.0)]
+    );
+
+    let kept = Dictionary::from_csv(
+        content,
+        &DictionaryFormat {
+            normalization: Normalization::Keep,
+            ..DictionaryFormat::lexique()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        kept.words(6, "g"),
+        [("garcon".to_string(), 1.0), ("garçon".to_string(), 2.0)]
+    );
+    assert_eq!(
+        kept.words(4, ""),
+        [("cœur".to_string(), 3.0), ("sœur".to_string(), 1.0)]
+    );
+    assert_eq!(kept.normalize("garçon"), "garçon");
+    assert_eq!(folded.normalize("garçon"), "garcon");
+    // The first letter is normalized like the words.
+    assert_eq!(folded.words(5, "ç"), [("coeur".to_string(), 3.0)]);
+    let mapped = Dictionary::from_csv(
+        "word\nçava\ncava\n",
+        &DictionaryFormat {
+            normalization: "ç=c".parse().unwrap(),
+            ..DictionaryFormat::new(';', "word", None)
+        },
+    )
+    .unwrap();
+    assert_eq!(mapped.words(4, "ç"), [("cava".to_string(), 2.0)]);
+}
+
+const LEXIQUE_EXCERPT: &str = "1_ortho;2_phon;3_lemme;4_cgram;5_genre;6_nombre;10_freqlivres
+porte;pORt;porte;NOM;f;s;120.5
+porte;pORt;porter;VER;;;40
+portes;pORt;porte;NOM;f;p;20
+portes;pORt;porter;VER;;;5
+forte;fORt;fort;ADJ;f;s;30
+fort;fOR;fort;ADJ;m;s;50
+";
+
+#[test]
+fn Lexique_grammar_columns() {
+    let dictionary = Dictionary::from_csv(LEXIQUE_EXCERPT, &DictionaryFormat::lexique()).unwrap();
+    assert_eq!(dictionary.words(5, "p"), [("porte".to_string(), 160.5)]);
+    assert_eq!(
+        dictionary.lexemes("porte"),
+        [
+            Lexeme {
+                lemma: "porte".to_string(),
+                category: "NOM".to_string(),
+                gender: "f".to_string(),
+                number: "s".to_string(),
+            },
+            Lexeme {
+                lemma: "porter".to_string(),
+                category: "VER".to_string(),
+                ..Lexeme::default()
+            }
+        ]
+    );
+    assert!(dictionary.lexemes("tarie").is_empty());
+}
+
+#[test]
+fn Restrict_answers_by_grammar() {
+    let mut dictionary =
+        Dictionary::from_csv(LEXIQUE_EXCERPT, &DictionaryFormat::lexique()).unwrap();
+    assert!(dictionary.has_grammar());
+    let answers = |dictionary: &Dictionary| {
+        ["porte", "portes", "forte", "fort"]
+            .into_iter()
+            .filter(|word| dictionary.is_answer(word))
+            .collect_vec()
+    };
+    assert_eq!(answers(&dictionary).len(), 4);
+
+    dictionary
+        .restrict_answers(&AnswerFilter {
+            lemmas_only: true,
+            ..AnswerFilter::default()
+        })
+        .unwrap();
+    assert_eq!(answers(&dictionary), ["porte", "fort"]);
+
+    dictionary
+        .restrict_answers(&AnswerFilter {
+            categories: vec!["nom".to_string()],
+            singular_only: true,
+            ..AnswerFilter::default()
+        })
+        .unwrap();
+    assert_eq!(answers(&dictionary), ["porte"]);
+    // Every word remains a guess.
+    assert_eq!(dictionary.words(6, "").len(), 1);
+
+    dictionary
+        .restrict_answers(&AnswerFilter::default())
+        .unwrap();
+    assert_eq!(answers(&dictionary).len(), 4);
+}
+
+#[test]
+fn Restrict_answers_needs_grammar() {
+    let mut dictionary = Dictionary::from_csv(
+        "1_ortho;10_freqlivres\ntarie;1\n",
+        &DictionaryFormat::lexique(),
+    )
+    .unwrap();
+    assert!(!dictionary.has_grammar());
+    assert!(matches!(
+        dictionary.restrict_answers(&AnswerFilter {
+            lemmas_only: true,
+            ..AnswerFilter::default()
+        }),
+        Err(DictionaryError::NoGrammar)
+    ));
+    assert!(dictionary.is_answer("tarie"));
+}
+
+#[test]
+fn Lexique_columns_of_a_complete_file() {
+    let complete = LEXIQUE_EXCERPT.replace(';', "\t");
+    let embedded = lexique_columns(&complete, '\t').unwrap();
+    assert!(embedded.starts_with("1_ortho;3_lemme;4_cgram;5_genre;6_nombre;10_freqlivres\n"));
+    assert_eq!(embedded.lines().nth(1), Some("porte;porte;NOM;f;s;120.5"));
+    assert_eq!(
+        Dictionary::from_csv(&embedded, &DictionaryFormat::lexique()).unwrap(),
+        Dictionary::from_csv(LEXIQUE_EXCERPT, &DictionaryFormat::lexique()).unwrap()
+    );
+    assert!(matches!(
+        lexique_columns("1_ortho;10_freqlivres\n", ';'),
+        Err(DictionaryError::MissingColumn(column)) if column == "3_lemme"
+    ));
+}
+
+#[test]
+#[ignore = "the embedded Lexique381.csv has to be regenerated with `rustle-cli lexique` first"]
+fn Restrict_lexique_answers() {
+    let mut dictionary = Dictionary::lexique().clone();
+    dictionary
+        .restrict_answers(&AnswerFilter {
+            categories: vec!["NOM".to_string()],
+            lemmas_only: true,
+            singular_only: true,
+        })
+        .unwrap();
+    assert!(dictionary.is_answer("porte"));
+    assert!(!dictionary.is_answer("portes"));
+    assert!(!dictionary.is_answer("tarie"));
+    assert!(dictionary
+        .words(5, "t")
+        .iter()
+        .any(|(word, _)| word == "tarie"));
+}
@@ -0,0 +1,26 @@
+//! Dictionaries shared by the module tests.
+use crate::WordleDictionary::Dictionary;
+
+/// Five letter words where `tarie` and `taris` only differ by their last
+/// letter and `blocu` shares no letter with them.
+pub const WORDS: [&str; 5] = ["tarie", "taris", "poele", "crise", "blocu"];
+
+/// The fixture words, all equally frequent.
+pub fn dictionary() -> Dictionary {
+    Dictionary::from_words(WORDS.map(|word| (word, 1.0)))
+}
+
+/// The fixture words from the most to the least frequent.
+pub fn weighted_dictionary() -> Dictionary {
+    Dictionary::from_words(WORDS.into_iter().zip([10.0, 5.0, 2.0, 0.5, 0.1]))
+}
+
+/// The fixture words but `blocu`, which is left to be guessed as a non-word.
+pub fn dictionary_without_blocu() -> Dictionary {
+    Dictionary::from_words(
+        WORDS
+            .into_iter()
+            .filter(|&word| word != "blocu")
+            .map(|word| (word, 1.0)),
+    )
+}
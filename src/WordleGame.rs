@@ -0,0 +1,217 @@
+use crate::WordleDaily::{self, DailyError, DailyOptions};
+use crate::WordleDictionary::Dictionary;
+use crate::WordleSolver::{self, Pattern, SolverError};
+use chrono::NaiveDate;
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::fmt;
+
+/// Secret words are only drawn among words at least this frequent, so that they are known words.
+pub const DEFAULT_MIN_FREQUENCY: f32 = 1.0;
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct GameOptions {
+    pub word_length: usize,
+    /// First letter of the secret word, revealed like in Sutom. Any letter if empty.
+    pub first_char: String,
+    pub max_guesses: usize,
+    /// Minimum frequency of the secret word, guesses may be any word of the dictionary.
+    pub min_frequency: f32,
+}
+
+impl GameOptions {
+    #[must_use]
+    pub fn new(word_length: usize, first_char: &str) -> Self {
+        Self {
+            word_length,
+            first_char: first_char.to_string(),
+            max_guesses: crate::WordleSimulation::DEFAULT_MAX_GUESSES,
+            min_frequency: DEFAULT_MIN_FREQUENCY,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum GameState {
+    InProgress,
+    Won,
+    Lost,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum GameError {
+    /// No word of the dictionary fits the game options.
+    NoSecretWord,
+    GameOver,
+    InvalidGuess(SolverError),
+    Daily(DailyError),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSecretWord => write!(f, "No word of the dictionary fits the game options"),
+            Self::GameOver => write!(f, "The game is over, start a new one"),
+            Self::InvalidGuess(error) => error.fmt(f),
+            Self::Daily(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<SolverError> for GameError {
+    fn from(error: SolverError) -> Self {
+        Self::InvalidGuess(error)
+    }
+}
+
+impl From<DailyError> for GameError {
+    fn from(error: DailyError) -> Self {
+        Self::Daily(error)
+    }
+}
+
+/// Picks the secret word among the answers of the options frequent enough, using `seed`
+/// so that the same seed always gives the same word.
+#[must_use]
+pub fn pick_secret(dictionary: &Dictionary, options: &GameOptions, seed: u64) -> Option<String> {
+    let words = dictionary
+        .words(options.word_length, &options.first_char)
+        .iter()
+        .filter(|(word, freq)| *freq >= options.min_frequency && dictionary.is_answer(word))
+        .collect_vec();
+    let mut rng = StdRng::seed_from_u64(seed);
+    words.choose(&mut rng).map(|(word, _)| word.clone())
+}
+
+/// A game of Wordle, or Sutom when the first letter is given, against a secret word.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Game {
+    options: GameOptions,
+    secret: String,
+    guesses: Vec<(String, Vec<Pattern>)>,
+}
+
+impl Game {
+    /// # Errors
+    /// Returns `GameError::NoSecretWord` if no word fits the options.
+    pub fn with_seed(
+        dictionary: &Dictionary,
+        options: GameOptions,
+        seed: u64,
+    ) -> Result<Self, GameError> {
+        let secret = pick_secret(dictionary, &options, seed).ok_or(GameError::NoSecretWord)?;
+        Ok(Self::with_secret(options, secret))
+    }
+
+    /// # Errors
+    /// Returns `GameError::NoSecretWord` if no word fits the options.
+    pub fn random(dictionary: &Dictionary, options: GameOptions) -> Result<Self, GameError> {
+        Self::with_seed(dictionary, options, rand::random())
+    }
+
+    /// Game of the day: every player gets the same secret word on `date`, see `word_of_the_day`.
+    /// # Errors
+    /// Returns `GameError::Daily` if the daily options cannot give a word for `date`.
+    pub fn daily(
+        dictionary: &Dictionary,
+        daily: &DailyOptions,
+        date: NaiveDate,
+    ) -> Result<Self, GameError> {
+        let secret = WordleDaily::word_of_the_day(dictionary, date, daily)?;
+        let options = GameOptions {
+            min_frequency: daily.min_frequency,
+            ..GameOptions::new(daily.word_length, &daily.first_char)
+        };
+        Ok(Self::with_secret(options, secret))
+    }
+
+    #[must_use]
+    pub fn with_secret(options: GameOptions, secret: String) -> Self {
+        Self {
+            options,
+            secret,
+            guesses: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn options(&self) -> &GameOptions {
+        &self.options
+    }
+
+    /// Guessed words with their pattern, in order.
+    #[must_use]
+    pub fn guesses(&self) -> &[(String, Vec<Pattern>)] {
+        &self.guesses
+    }
+
+    /// The guesses in the WORD:PATTERN steps the solver reads.
+    #[must_use]
+    pub fn steps(&self) -> Vec<(String, String)> {
+        self.guesses
+            .iter()
+            .map(|(word, pattern)| (word.clone(), WordleSolver::format_pattern(pattern)))
+            .collect()
+    }
+
+    /// The secret word, only revealed once the game is over.
+    #[must_use]
+    pub fn secret(&self) -> Option<&str> {
+        (self.state() != GameState::InProgress).then_some(self.secret.as_str())
+    }
+
+    #[must_use]
+    pub fn state(&self) -> GameState {
+        if self
+            .guesses
+            .last()
+            .is_some_and(|(word, _)| *word == self.secret)
+        {
+            GameState::Won
+        } else if self.guesses.len() >= self.options.max_guesses {
+            GameState::Lost
+        } else {
+            GameState::InProgress
+        }
+    }
+
+    /// Scores `word`, normalized as the dictionary words, against the secret word with `get_pattern`.
+    /// # Errors
+    /// Returns `GameError::GameOver` once the game is won or lost, and `GameError::InvalidGuess`
+    /// if the word is not alphabetic, does not have the game length or is not in the dictionary
+    /// (starting with the game first letter).
+    pub fn guess(
+        &mut self,
+        dictionary: &Dictionary,
+        word: &str,
+    ) -> Result<Vec<Pattern>, GameError> {
+        if self.state() != GameState::InProgress {
+            return Err(GameError::GameOver);
+        }
+        let word = dictionary.normalize(word.trim());
+        let word_length = self.options.word_length;
+        if !word.chars().all(char::is_alphabetic) {
+            return Err(SolverError::NonAlphabeticGuess { word }.into());
+        }
+        if word.chars().count() != word_length {
+            return Err(SolverError::WordLengthMismatch { word, word_length }.into());
+        }
+        let words = dictionary.words(word_length, &self.options.first_char);
+        if words
+            .binary_search_by(|(known, _)| known.cmp(&word))
+            .is_err()
+        {
+            return Err(SolverError::UnknownWord { word }.into());
+        }
+        let pattern = WordleSolver::get_pattern(&word, &self.secret);
+        self.guesses.push((word, pattern.clone()));
+        Ok(pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,100 @@
+use super::*;
+use crate::WordleFixtures::weighted_dictionary;
+
+#[test]
+fn Secret_is_seeded_and_frequent() {
+    let dictionary = weighted_dictionary();
+    let options = GameOptions::new(5, "");
+    for seed in 0..20 {
+        let secret = pick_secret(&dictionary, &options, seed).unwrap();
+        assert_eq!(
+            Some(secret.clone()),
+            pick_secret(&dictionary, &options, seed)
+        );
+        assert!(["tarie", "taris", "poele"].contains(&secret.as_str()));
+    }
+    assert_eq!(
+        pick_secret(&dictionary, &GameOptions::new(5, "p"), 3),
+        Some("poele".to_string())
+    );
+    assert_eq!(pick_secret(&dictionary, &GameOptions::new(6, ""), 3), None);
+    assert_eq!(
+        Game::with_seed(&dictionary, GameOptions::new(5, "c"), 3),
+        Err(GameError::NoSecretWord)
+    );
+}
+
+#[test]
+fn Game_is_won() {
+    let dictionary = weighted_dictionary();
+    let mut game = Game::with_secret(GameOptions::new(5, ""), "taris".to_string());
+    assert_eq!(
+        game.guess(&dictionary, "tarie").unwrap(),
+        WordleSolver::get_pattern("tarie", "taris")
+    );
+    assert_eq!(game.state(), GameState::InProgress);
+    assert_eq!(game.secret(), None);
+    assert_eq!(
+        game.steps(),
+        vec![("tarie".to_string(), "22220".to_string())]
+    );
+    // Guesses are folded like the dictionary words.
+    assert_eq!(
+        game.guess(&dictionary, "Pöêle").unwrap(),
+        WordleSolver::get_pattern("poele", "taris")
+    );
+    game.guess(&dictionary, " TARIS ").unwrap();
+    assert_eq!(game.state(), GameState::Won);
+    assert_eq!(game.secret(), Some("taris"));
+    assert_eq!(game.guess(&dictionary, "poele"), Err(GameError::GameOver));
+}
+
+#[test]
+fn Game_is_lost() {
+    let dictionary = weighted_dictionary();
+    let options = GameOptions {
+        max_guesses: 2,
+        ..GameOptions::new(5, "")
+    };
+    let mut game = Game::with_secret(options, "taris".to_string());
+    game.guess(&dictionary, "poele").unwrap();
+    game.guess(&dictionary, "crise").unwrap();
+    assert_eq!(game.state(), GameState::Lost);
+    assert_eq!(game.secret(), Some("taris"));
+}
+
+#[test]
+fn Game_rejects_invalid_guesses() {
+    let dictionary = weighted_dictionary();
+    let mut game = Game::with_secret(GameOptions::new(5, "t"), "taris".to_string());
+    assert_eq!(
+        game.guess(&dictionary, "poele"),
+        Err(GameError::InvalidGuess(SolverError::UnknownWord {
+            word: "poele".to_string()
+        }))
+    );
+    assert!(matches!(
+        game.guess(&dictionary, "tari"),
+        Err(GameError::InvalidGuess(
+            SolverError::WordLengthMismatch { .. }
+        ))
+    ));
+    assert!(matches!(
+        game.guess(&dictionary, "tar1e"),
+        Err(GameError::InvalidGuess(
+            SolverError::NonAlphabeticGuess { .. }
+        ))
+    ));
+    assert!(game.guesses().is_empty());
+}
+
+#[test]
+fn Daily_game() {
+    let daily = DailyOptions::new(5, "t");
+    let date = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+    let mut game = Game::daily(Dictionary::lexique(), &daily, date).unwrap();
+    let secret = WordleDaily::word_of_the_day(Dictionary::lexique(), date, &daily).unwrap();
+    game.guess(Dictionary::lexique(), &secret).unwrap();
+    assert_eq!(game.state(), GameState::Won);
+    assert_eq!(game.options().first_char, "t");
+}
@@ -0,0 +1,176 @@
+use crate::WordleDictionary::{
+    AnswerFilter, Dictionary, DictionaryError, DictionaryFormat, Normalization, LATIN_ALPHABET,
+};
+use std::borrow::Cow;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Codes of the built-in profiles, see `LanguageProfile::builtin`. Only French ships its words,
+/// the other profiles describe a word list the player supplies.
+pub const LANGUAGE_CODES: [&str; 4] = ["fr", "en", "es", "de"];
+
+/// Everything needed to play in a language: where its words come from, the layout of the
+/// word list with its frequency column, the letters the words may use and how their accents
+/// are normalized.
+#[derive(PartialEq, Clone, Debug)]
+pub struct LanguageProfile {
+    pub code: String,
+    pub name: String,
+    /// Word list to read, the embedded Lexique 3.81 when `None` for French.
+    pub path: Option<PathBuf>,
+    pub format: DictionaryFormat,
+    /// Words that may be the answer, read from the grammatical columns of the word list.
+    pub answers: AnswerFilter,
+}
+
+#[derive(Debug)]
+pub enum LanguageError {
+    UnknownLanguage(String),
+    /// Only French has an embedded word list, the other profiles need a file.
+    MissingWordList(String),
+    Dictionary(DictionaryError),
+}
+
+impl fmt::Display for LanguageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownLanguage(code) => write!(
+                f,
+                "Unknown language '{code}', expected one of {}",
+                LANGUAGE_CODES.join(", ")
+            ),
+            Self::MissingWordList(code) => write!(
+                f,
+                "No word list is embedded for '{code}', give the path of one"
+            ),
+            Self::Dictionary(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LanguageError {}
+
+impl From<DictionaryError> for LanguageError {
+    fn from(error: DictionaryError) -> Self {
+        Self::Dictionary(error)
+    }
+}
+
+impl LanguageProfile {
+    /// Profile of a language without embedded words, reading a `word;frequency` list by default.
+    fn user_supplied(code: &str, name: &str, alphabet: &str, normalization: Normalization) -> Self {
+        Self {
+            code: code.to_string(),
+            name: name.to_string(),
+            path: None,
+            format: DictionaryFormat {
+                alphabet: Some(alphabet.to_string()),
+                normalization,
+                ..DictionaryFormat::new(';', "word", Some("frequency"))
+            },
+            answers: AnswerFilter::default(),
+        }
+    }
+
+    /// French, played on the embedded Lexique 3.81 with its `freqlivres` frequencies.
+    #[must_use]
+    pub fn french() -> Self {
+        Self {
+            code: "fr".to_string(),
+            name: "Français".to_string(),
+            path: None,
+            format: DictionaryFormat::lexique(),
+            answers: AnswerFilter::default(),
+        }
+    }
+
+    /// English, from a `word;frequency` list: no English words are shipped.
+    #[must_use]
+    pub fn english() -> Self {
+        Self::user_supplied("en", "English", LATIN_ALPHABET, Normalization::Fold)
+    }
+
+    /// Spanish drops the accents but keeps ñ, a letter of its own. No Spanish words are shipped.
+    #[must_use]
+    pub fn spanish() -> Self {
+        let mapping = [
+            ('á', "a"),
+            ('é', "e"),
+            ('í', "i"),
+            ('ó', "o"),
+            ('ú', "u"),
+            ('ü', "u"),
+        ];
+        Self::user_supplied(
+            "es",
+            "Español",
+            &format!("{LATIN_ALPHABET}ñáéíóúü"),
+            custom(&mapping),
+        )
+    }
+
+    /// German writes the umlauts and ß out, as in crosswords. No German words are shipped.
+    #[must_use]
+    pub fn german() -> Self {
+        let mapping = [('ä', "ae"), ('ö', "oe"), ('ü', "ue"), ('ß', "ss")];
+        Self::user_supplied(
+            "de",
+            "Deutsch",
+            &format!("{LATIN_ALPHABET}äöüß"),
+            custom(&mapping),
+        )
+    }
+
+    /// The profile of one of `LANGUAGE_CODES`, with a path to set before loading the words
+    /// of any other language than French.
+    /// # Errors
+    /// Returns `LanguageError::UnknownLanguage` for any other code.
+    pub fn builtin(code: &str) -> Result<Self, LanguageError> {
+        match code.trim().to_lowercase().as_str() {
+            "fr" => Ok(Self::french()),
+            "en" => Ok(Self::english()),
+            "es" => Ok(Self::spanish()),
+            "de" => Ok(Self::german()),
+            _ => Err(LanguageError::UnknownLanguage(code.to_string())),
+        }
+    }
+
+    /// Reads the words of the profile and restricts its answers. Without a path, the French
+    /// profile borrows the embedded Lexique, or reads it again when its format was changed.
+    /// # Errors
+    /// Returns `LanguageError::MissingWordList` if another profile has no path, or
+    /// `LanguageError::Dictionary` if its file cannot be read or has no grammatical columns
+    /// to restrict the answers with, as the embedded Lexique.
+    pub fn load(&self) -> Result<Cow<'static, Dictionary>, LanguageError> {
+        let dictionary = self.load_words()?;
+        if self.answers.is_empty() {
+            return Ok(dictionary);
+        }
+        let mut dictionary = dictionary.into_owned();
+        dictionary.restrict_answers(&self.answers)?;
+        Ok(Cow::Owned(dictionary))
+    }
+
+    fn load_words(&self) -> Result<Cow<'static, Dictionary>, LanguageError> {
+        match &self.path {
+            Some(path) => Ok(Cow::Owned(Dictionary::from_path(path, &self.format)?)),
+            None if self.code == "fr" && self.format == DictionaryFormat::lexique() => {
+                Ok(Cow::Borrowed(Dictionary::lexique()))
+            }
+            None if self.code == "fr" => Ok(Cow::Owned(Dictionary::lexique_with(&self.format)?)),
+            None => Err(LanguageError::MissingWordList(self.code.clone())),
+        }
+    }
+}
+
+fn custom(mapping: &[(char, &str)]) -> Normalization {
+    Normalization::Custom(
+        mapping
+            .iter()
+            .map(|&(from, to)| (from, to.to_string()))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,92 @@
+use super::*;
+
+#[test]
+fn Builtin_profiles() {
+    for code in LANGUAGE_CODES {
+        assert_eq!(LanguageProfile::builtin(code).unwrap().code, code);
+    }
+    assert_eq!(
+        LanguageProfile::builtin("FR").unwrap(),
+        LanguageProfile::french()
+    );
+    assert!(matches!(
+        LanguageProfile::builtin("it"),
+        Err(LanguageError::UnknownLanguage(code)) if code == "it"
+    ));
+}
+
+#[test]
+fn French_profile_borrows_lexique() {
+    let dictionary = LanguageProfile::french().load().unwrap();
+    assert!(
+        matches!(dictionary, Cow::Borrowed(lexique) if std::ptr::eq(lexique, Dictionary::lexique()))
+    );
+}
+
+#[test]
+fn Profiles_without_words_need_a_file() {
+    assert!(matches!(
+        LanguageProfile::english().load(),
+        Err(LanguageError::MissingWordList(code)) if code == "en"
+    ));
+    let path = std::env::temp_dir().join("rustle_english_profile.csv");
+    std::fs::write(
+        &path,
+        "word;frequency\nhello;3.5\nworld;2\nnaïve;1\nsigma2;1\n",
+    )
+    .unwrap();
+    let english = LanguageProfile {
+        path: Some(path.clone()),
+        ..LanguageProfile::english()
+    };
+    let dictionary = english.load().unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(
+        dictionary.words(5, ""),
+        [
+            ("hello".to_string(), 3.5),
+            ("naive".to_string(), 1.0),
+            ("world".to_string(), 2.0)
+        ]
+    );
+}
+
+#[test]
+fn Profiles_normalize_their_letters() {
+    let spanish = LanguageProfile::spanish().format.normalization;
+    assert_eq!(spanish.normalize("niño"), "niño");
+    assert_eq!(spanish.normalize("canción"), "cancion");
+    let german = LanguageProfile::german().format.normalization;
+    assert_eq!(german.normalize("größe"), "groesse");
+}
+
+#[test]
+fn Answers_need_a_complete_lexique() {
+    let french = LanguageProfile {
+        answers: AnswerFilter {
+            categories: vec!["NOM".to_string(), "ADJ".to_string()],
+            ..AnswerFilter::default()
+        },
+        ..LanguageProfile::french()
+    };
+    assert!(matches!(
+        french.load(),
+        Err(LanguageError::Dictionary(DictionaryError::NoGrammar))
+    ));
+    let path = std::env::temp_dir().join("rustle_complete_lexique.csv");
+    std::fs::write(
+        &path,
+        "1_ortho;3_lemme;4_cgram;10_freqlivres\nporte;porte;NOM;120\nporte;porter;VER;40\nporta;porter;VER;10\n",
+    )
+    .unwrap();
+    let dictionary = LanguageProfile {
+        path: Some(path.clone()),
+        ..french
+    }
+    .load()
+    .unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(dictionary.words(5, "").len(), 2);
+    assert!(dictionary.is_answer("porte"));
+    assert!(!dictionary.is_answer("porta"));
+}
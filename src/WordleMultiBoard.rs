@@ -0,0 +1,183 @@
+use crate::WordleDictionary::Dictionary;
+use crate::WordleSolver::{self, ScoringMode, SolverError, SolverOptions, WordleEntity};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// Number of boards of the Wordle, Dordle, Quordle and Octordle variants.
+pub const BOARD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Board {
+    /// One of the steps was all correct, the board does not need guesses anymore.
+    pub solved: bool,
+    /// Words matching every step of the board, as scored by `retrieve_recommended_words`,
+    /// none once the board is solved.
+    pub candidates: Vec<WordleEntity>,
+    /// No answer matches the steps of the board, see `WordleSolver::outside_answers`.
+    pub outside_answers: bool,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct MultiBoardRecommendations {
+    pub boards: Vec<Board>,
+    /// Guesses ranked by their combined scores, see `retrieve_multi_board_recommendations`.
+    pub recommended: Vec<WordleEntity>,
+}
+
+/// Whether one of the steps was all correct.
+#[must_use]
+pub fn is_solved(steps: &[(String, String)]) -> bool {
+    steps.iter().any(|(_, pattern)| is_solving(pattern))
+}
+
+/// Whether the pattern is all correct.
+fn is_solving(pattern: &str) -> bool {
+    !pattern.is_empty() && pattern.chars().all(|c| c == '2')
+}
+
+/// Replaces the word guessed at `index` on every board that got it, keeping their patterns.
+/// The word is normalized as the words of the dictionary.
+/// # Errors
+/// Returns the `SolverError` of `parse_step` if the word does not fit one of the boards,
+/// in which case no board is changed.
+pub fn replace_guess(
+    dictionary: &Dictionary,
+    boards: &mut [Vec<(String, String)>],
+    index: usize,
+    word: &str,
+    word_length: usize,
+) -> Result<(), SolverError> {
+    let word = dictionary.normalize(word.trim());
+    for steps in boards.iter().filter(|steps| steps.len() > index) {
+        WordleSolver::parse_step(&word, &steps[index].1, word_length)?;
+    }
+    for steps in boards.iter_mut().filter(|steps| steps.len() > index) {
+        steps[index].0.clone_from(&word);
+    }
+    Ok(())
+}
+
+/// Replaces the pattern of the step at `index` of a board, unless that step solves the board
+/// or would solve it: the boards share their guesses until they are solved, so only a new
+/// guess or `remove_guess` changes when a board is solved. Returns whether it was replaced.
+#[must_use]
+pub fn replace_pattern(steps: &mut [(String, String)], index: usize, pattern: &str) -> bool {
+    match steps.get_mut(index) {
+        Some((_, old)) if !is_solving(old) && !is_solving(pattern) => {
+            *old = pattern.to_string();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Removes the word guessed at `index` from every board that got it. A board solved by that
+/// guess is given the later guesses back, with their patterns against its answer, so that the
+/// boards keep sharing their guesses until they are solved.
+pub fn remove_guess(boards: &mut [Vec<(String, String)>], index: usize) {
+    let guesses = boards
+        .iter()
+        .max_by_key(|steps| steps.len())
+        .map(|steps| steps.iter().map(|(word, _)| word.clone()).collect_vec())
+        .unwrap_or_default();
+    for steps in boards.iter_mut().filter(|steps| steps.len() > index) {
+        let (answer, pattern) = steps.remove(index);
+        if !is_solving(&pattern) {
+            continue;
+        }
+        for guess in guesses.iter().skip(index + 1) {
+            if is_solved(steps) {
+                break;
+            }
+            let pattern = WordleSolver::format_pattern(&WordleSolver::get_pattern(guess, &answer));
+            steps.push((guess.clone(), pattern));
+        }
+    }
+}
+
+/// Solves several boards whose answers are guessed together, each board having its own
+/// steps for the same guessed words. Only the unsolved boards are scored, and the scores of
+/// a guess are summed over them, assuming their answers are independent: `entropy` is the
+/// combined entropy, `expected_guesses` and `expected_bucket` the totals over the boards,
+/// and a guess is a candidate if it may be the answer of any of them, with the highest of
+/// its probabilities and the largest of its worst-case buckets. The guesses are ranked with
+/// `options.scoring` on these combined scores, except that `ScoringMode::TwoStep` ranks them
+/// by combined entropy: its lookahead is not run on each board.
+/// # Errors
+/// Returns a `SolverError` as soon as one of the steps of any board is invalid, see `parse_step`.
+pub fn retrieve_multi_board_recommendations(
+    dictionary: &Dictionary,
+    boards: &[Vec<(String, String)>],
+    word_length: usize,
+    first_char: &str,
+    options: &SolverOptions,
+) -> Result<MultiBoardRecommendations, SolverError> {
+    let board_options = SolverOptions {
+        scoring: ScoringMode::Entropy,
+        ..options.clone()
+    };
+    // Combined scores of each guess, with the number of boards that scored it.
+    let mut combined: HashMap<String, (WordleEntity, usize)> = HashMap::new();
+    let mut unsolved = 0;
+    let boards = boards
+        .iter()
+        .map(|steps| {
+            if is_solved(steps) {
+                for (word, pattern) in steps {
+                    WordleSolver::parse_step(&dictionary.normalize(word), pattern, word_length)?;
+                }
+                return Ok(Board {
+                    solved: true,
+                    candidates: Vec::new(),
+                    outside_answers: false,
+                });
+            }
+            let recommended = WordleSolver::retrieve_recommended_words(
+                dictionary,
+                steps,
+                word_length,
+                first_char,
+                &board_options,
+            )?;
+            unsolved += 1;
+            for we in &recommended {
+                combined
+                    .entry(we.word.clone())
+                    .and_modify(|(total, count)| {
+                        total.entropy += we.entropy;
+                        total.expected_guesses += we.expected_guesses;
+                        total.expected_bucket += we.expected_bucket;
+                        total.is_candidate |= we.is_candidate;
+                        total.probability = total.probability.max(we.probability);
+                        total.largest_bucket = total.largest_bucket.max(we.largest_bucket);
+                        *count += 1;
+                    })
+                    .or_insert_with(|| (we.clone(), 1));
+            }
+            let candidates = recommended
+                .into_iter()
+                .filter(|we| we.is_candidate)
+                .collect_vec();
+            Ok(Board {
+                solved: false,
+                outside_answers: WordleSolver::outside_answers(dictionary, &candidates),
+                candidates,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut recommended = combined
+        .into_values()
+        // In hard mode, a guess has to match the steps of every unsolved board.
+        .filter(|(_, count)| !options.hard_mode || *count == unsolved)
+        .map(|(total, _)| total)
+        .sorted_by(|a, b| a.word.cmp(&b.word))
+        .collect_vec();
+    options.scoring.sort(&mut recommended);
+    Ok(MultiBoardRecommendations {
+        boards,
+        recommended,
+    })
+}
+
+#[cfg(test)]
+mod tests;
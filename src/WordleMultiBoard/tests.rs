@@ -0,0 +1,159 @@
+#![allow(clippy::float_cmp)]
+use super::*;
+use crate::WordleFixtures;
+
+fn steps(steps: &[(&str, &str)]) -> Vec<(String, String)> {
+    steps
+        .iter()
+        .map(|(word, pattern)| (word.to_string(), pattern.to_string()))
+        .collect()
+}
+
+#[test]
+fn Single_board_matches_solver() {
+    let boards = [steps(&[("tarie", "20100")])];
+    let test = retrieve_multi_board_recommendations(
+        Dictionary::lexique(),
+        &boards,
+        5,
+        "t",
+        &SolverOptions::default(),
+    )
+    .unwrap();
+    let mut expected = WordleSolver::retrieve_recommended_words(
+        Dictionary::lexique(),
+        &boards[0],
+        5,
+        "t",
+        &SolverOptions::default(),
+    )
+    .unwrap();
+    expected.sort_by(|a, b| b.entropy.partial_cmp(&a.entropy).unwrap());
+    assert_eq!(test.recommended[0].word, expected[0].word);
+    assert_eq!(test.recommended[0].entropy, expected[0].entropy);
+    assert_eq!(
+        test.boards[0].candidates.len(),
+        expected.iter().filter(|we| we.is_candidate).count()
+    );
+}
+
+#[test]
+fn Entropy_is_summed_over_unsolved_boards() {
+    let dictionary = WordleFixtures::dictionary_without_blocu();
+    let options = SolverOptions::default();
+    let boards = [
+        steps(&[("blocu", "00000")]),
+        steps(&[("blocu", "01100")]),
+        steps(&[("tarie", "22222")]),
+    ];
+    let test = retrieve_multi_board_recommendations(&dictionary, &boards, 5, "", &options).unwrap();
+    assert_eq!(
+        test.boards.iter().map(|board| board.solved).collect_vec(),
+        vec![false, false, true]
+    );
+    assert_eq!(test.boards[1].candidates.len(), 1);
+    assert_eq!(test.boards[1].candidates[0].word, "poele");
+    // Only the first board has candidates to split, poele is the answer of the second.
+    let poele = test
+        .recommended
+        .iter()
+        .find(|we| we.word == "poele")
+        .unwrap();
+    assert!(poele.is_candidate);
+    assert_eq!(poele.probability, 1.0);
+    let first =
+        WordleSolver::retrieve_recommended_words(&dictionary, &boards[0], 5, "", &options).unwrap();
+    for we in &test.recommended {
+        let single = first.iter().find(|single| single.word == we.word).unwrap();
+        assert_eq!(we.entropy, single.entropy);
+    }
+}
+
+#[test]
+fn Hard_mode_matches_every_board() {
+    let options = SolverOptions {
+        hard_mode: true,
+        ..SolverOptions::default()
+    };
+    let boards = [steps(&[("tarie", "20000")]), steps(&[("tarie", "20100")])];
+    let test =
+        retrieve_multi_board_recommendations(Dictionary::lexique(), &boards, 5, "t", &options)
+            .unwrap();
+    // No word can both lack and contain an r.
+    assert!(test.recommended.is_empty());
+}
+
+#[test]
+fn Scoring_mode_ranks_combined_scores() {
+    let boards = [
+        steps(&[("tarie", "20000")]),
+        steps(&[("tarie", "20100")]),
+        steps(&[("taris", "22222")]),
+    ];
+    let scored = |scoring| {
+        let options = SolverOptions {
+            scoring,
+            ..SolverOptions::default()
+        };
+        retrieve_multi_board_recommendations(Dictionary::lexique(), &boards, 5, "t", &options)
+            .unwrap()
+    };
+    let minimax = scored(ScoringMode::Minimax);
+    assert!(minimax
+        .recommended
+        .windows(2)
+        .all(|pair| pair[0].largest_bucket <= pair[1].largest_bucket));
+    assert!(minimax.boards[2].solved);
+    assert!(minimax.boards[2].candidates.is_empty());
+    // The lookahead is not run on each board, two-step ranks by combined entropy.
+    let two_step = scored(ScoringMode::TwoStep);
+    assert!(two_step
+        .recommended
+        .iter()
+        .all(|we| we.two_step_entropy.is_none()));
+    assert_eq!(
+        two_step.recommended,
+        scored(ScoringMode::Entropy).recommended
+    );
+}
+
+#[test]
+fn Guesses_are_edited_on_every_board() {
+    let mut boards = vec![
+        steps(&[("tarie", "22222")]),
+        steps(&[("tarie", "22220"), ("taris", "22222")]),
+        steps(&[("tarie", "00001"), ("taris", "00000"), ("poele", "00002")]),
+    ];
+    assert!(replace_guess(Dictionary::lexique(), &mut boards, 1, "Tarot", 5).is_ok());
+    assert_eq!(boards[1][1].0, "tarot");
+    assert_eq!(boards[2][1].0, "tarot");
+    assert!(matches!(
+        replace_guess(Dictionary::lexique(), &mut boards, 1, "tarots", 5),
+        Err(SolverError::WordLengthMismatch { .. })
+    ));
+    assert_eq!(boards[1][1].0, "tarot");
+
+    // The first board was solved by tarie, it now gets the later guesses.
+    remove_guess(&mut boards, 0);
+    assert_eq!(
+        boards,
+        vec![
+            steps(&[("tarot", "22200"), ("poele", "00002")]),
+            steps(&[("tarot", "22222")]),
+            steps(&[("tarot", "00000"), ("poele", "00002")]),
+        ]
+    );
+}
+
+#[test]
+fn Patterns_keep_the_boards_solved_or_not() {
+    let mut steps = steps(&[("tarie", "22220"), ("taris", "22222")]);
+    assert!(replace_pattern(&mut steps, 0, "22221"));
+    assert_eq!(steps[0].1, "22221");
+    // Neither a step solving the board nor a step that would solve it is changed.
+    assert!(!replace_pattern(&mut steps, 0, "22222"));
+    assert!(!replace_pattern(&mut steps, 1, "22221"));
+    assert!(!replace_pattern(&mut steps, 2, "00000"));
+    assert_eq!(steps[0].1, "22221");
+    assert_eq!(steps[1].1, "22222");
+}
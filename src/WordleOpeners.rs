@@ -0,0 +1,364 @@
+use crate::WordleDictionary::Dictionary;
+use crate::WordleSimulation::{self, SimulationOptions};
+use crate::WordleSolver::{self, PatternMatrix, Prior, ScoringMode, SolverError, WordleEntity};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Version of the openers file format, files of another version are rejected.
+pub const OPENERS_VERSION: u32 = 2;
+/// Number of openers kept per table by default.
+pub const DEFAULT_TOP: usize = 5;
+
+const LEXIQUE_OPENERS: &str = include_str!("LexiqueOpeners.json");
+static LEXIQUE_OPENERS_TABLES: OnceLock<Openers> = OnceLock::new();
+
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct SimulatedOpener {
+    pub word: String,
+    pub average_guesses: f32,
+    /// Games needing more than `SimulationOptions::max_guesses` guesses.
+    pub failures: usize,
+}
+
+/// Best first guesses of the words of `word_length` letters starting with `first_char`,
+/// any when it is empty.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct OpenerTable {
+    pub word_length: usize,
+    pub first_char: String,
+    /// Number of words of the slice when the table was generated.
+    pub words: usize,
+    /// Digest of the words of the slice, their frequencies and whether they may be the answer,
+    /// see `slice_digest`. A table generated for other words is not served.
+    pub digest: String,
+    /// Best openers by entropy, scored with the default `SolverOptions`.
+    pub by_entropy: Vec<WordleEntity>,
+    /// The same openers ranked by the number of guesses the solver needs after them. Only the
+    /// openers of `by_entropy` are simulated, not every word of the slice: the best opener by
+    /// simulation may be another word.
+    pub by_simulation: Vec<SimulatedOpener>,
+    /// Most frequent candidates before the first step, scored as `by_entropy`.
+    pub possible: Vec<WordleEntity>,
+}
+
+/// Best guesses and candidates after some steps, see `recommendations`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Recommendations {
+    /// Guesses sorted with `SolverOptions::scoring`.
+    pub recommended: Vec<WordleEntity>,
+    /// Candidates, the most frequent first.
+    pub possible: Vec<WordleEntity>,
+    /// Number of words that could be guessed, the precomputed lists only hold the best ones.
+    pub recommended_count: usize,
+    /// Number of candidates, the precomputed lists only hold the most frequent ones.
+    pub possible_count: usize,
+    /// The lists come from the precomputed openers.
+    pub precomputed: bool,
+    /// No answer matches the steps, the candidates are the other words matching them,
+    /// see `WordleSolver::outside_answers`.
+    pub outside_answers: bool,
+}
+
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct Openers {
+    pub version: u32,
+    pub top: usize,
+    pub tables: Vec<OpenerTable>,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum OpenersError {
+    Json(String),
+    Version { found: u32, expected: u32 },
+}
+
+impl fmt::Display for OpenersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(message) => write!(f, "Invalid openers file: {message}"),
+            Self::Version { found, expected } => write!(
+                f,
+                "Openers file version {found} is not supported, regenerate it to version {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OpenersError {}
+
+impl From<serde_json::Error> for OpenersError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error.to_string())
+    }
+}
+
+impl Openers {
+    /// The openers of the embedded Lexique 3.81 word list, parsed on first use.
+    /// # Panics
+    /// Panics if the embedded `LexiqueOpeners.json` is not an openers file of `OPENERS_VERSION`,
+    /// e.g. when the version is bumped without regenerating it.
+    pub fn lexique() -> &'static Self {
+        LEXIQUE_OPENERS_TABLES.get_or_init(|| {
+            Self::from_json(LEXIQUE_OPENERS)
+                .expect("embedded LexiqueOpeners.json matches OPENERS_VERSION")
+        })
+    }
+
+    /// # Errors
+    /// Returns an `OpenersError` if the content is not an openers file of `OPENERS_VERSION`.
+    pub fn from_json(content: &str) -> Result<Self, OpenersError> {
+        let openers: Self = serde_json::from_str(content)?;
+        if openers.version != OPENERS_VERSION {
+            return Err(OpenersError::Version {
+                found: openers.version,
+                expected: OPENERS_VERSION,
+            });
+        }
+        Ok(openers)
+    }
+
+    /// JSON with one table per line, so that regenerating the file gives readable diffs.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_json(&self) -> String {
+        let tables = self
+            .tables
+            .iter()
+            .map(|table| {
+                serde_json::to_string(table).expect("tables only hold strings and numbers")
+            })
+            .collect_vec();
+        format!(
+            "{{\"version\":{},\"top\":{},\"tables\":[\n{}\n]}}\n",
+            self.version,
+            self.top,
+            tables.join(",\n")
+        )
+    }
+
+    #[must_use]
+    pub fn table(&self, word_length: usize, first_char: &str) -> Option<&OpenerTable> {
+        self.tables
+            .iter()
+            .find(|table| table.word_length == word_length && table.first_char == first_char)
+    }
+}
+
+/// Stable digest of the words of `word_length` letters starting with `first_char`, of their
+/// frequencies and of whether they may be the answer, written in hexadecimal.
+#[must_use]
+pub fn slice_digest(dictionary: &Dictionary, word_length: usize, first_char: &str) -> String {
+    // FNV-1a, whose value does not change between Rust releases as the std hasher may.
+    let mut digest: u64 = 0xcbf2_9ce4_8422_2325;
+    for (word, frequency) in dictionary.words(word_length, first_char) {
+        let bytes = word
+            .bytes()
+            .chain([0])
+            .chain(frequency.to_bits().to_le_bytes());
+        for byte in bytes.chain([u8::from(dictionary.is_answer(word))]) {
+            digest = (digest ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{digest:016x}")
+}
+
+/// Table of the embedded Lexique 3.81 openers for the slice, if it was generated for the same
+/// words and the options are those the openers were scored with: the entropy ranking and the
+/// uniform prior. Hard mode does not change the first guess.
+fn opener_table(
+    dictionary: &Dictionary,
+    word_length: usize,
+    first_char: &str,
+    options: &WordleSolver::SolverOptions,
+) -> Option<&'static OpenerTable> {
+    if options.scoring != ScoringMode::Entropy || options.prior != Prior::Uniform {
+        return None;
+    }
+    Openers::lexique()
+        .table(word_length, first_char)
+        .filter(|table| table.digest == slice_digest(dictionary, word_length, first_char))
+}
+
+/// Precomputed recommendations before the first step, sorted by entropy, see `opener_table`.
+#[must_use]
+pub fn opening_words(
+    dictionary: &Dictionary,
+    word_length: usize,
+    first_char: &str,
+    options: &WordleSolver::SolverOptions,
+) -> Option<Vec<WordleEntity>> {
+    opener_table(dictionary, word_length, first_char, options).map(|table| table.by_entropy.clone())
+}
+
+/// Recommended and possible words after `steps`. Before the first step, the precomputed
+/// openers are served instead of scoring every word when they hold at least `top` words
+/// of each list, see `opening_words`.
+/// # Errors
+/// Returns a `SolverError` as soon as one of the steps is invalid, see `parse_step`.
+pub fn recommendations(
+    dictionary: &Dictionary,
+    steps: &[(String, String)],
+    word_length: usize,
+    first_char: &str,
+    options: &WordleSolver::SolverOptions,
+    top: usize,
+) -> Result<Recommendations, SolverError> {
+    let table = opener_table(dictionary, word_length, first_char, options).filter(|table| {
+        steps.is_empty() && table.by_entropy.len().min(table.possible.len()) >= top
+    });
+    if let Some(table) = table {
+        let words = dictionary.words(word_length, first_char);
+        return Ok(Recommendations {
+            recommended: table.by_entropy.clone(),
+            possible: table.possible.clone(),
+            recommended_count: words.len(),
+            possible_count: words
+                .iter()
+                .filter(|(word, _)| dictionary.is_answer(word))
+                .count(),
+            precomputed: true,
+            outside_answers: false,
+        });
+    }
+    scored_recommendations(dictionary, steps, word_length, first_char, options)
+}
+
+/// Recommended and possible words after `steps`, scoring every word even before the first
+/// step, e.g. to sort all of them rather than the precomputed openers.
+/// # Errors
+/// Returns a `SolverError` as soon as one of the steps is invalid, see `parse_step`.
+pub fn scored_recommendations(
+    dictionary: &Dictionary,
+    steps: &[(String, String)],
+    word_length: usize,
+    first_char: &str,
+    options: &WordleSolver::SolverOptions,
+) -> Result<Recommendations, SolverError> {
+    let mut recommended = WordleSolver::retrieve_recommended_words(
+        dictionary,
+        steps,
+        word_length,
+        first_char,
+        options,
+    )?;
+    options.scoring.sort(&mut recommended);
+    let possible = most_frequent_candidates(&recommended);
+    Ok(Recommendations {
+        recommended_count: recommended.len(),
+        possible_count: possible.len(),
+        outside_answers: WordleSolver::outside_answers(dictionary, &possible),
+        recommended,
+        possible,
+        precomputed: false,
+    })
+}
+
+/// Candidates among the recommendations, the most frequent first.
+fn most_frequent_candidates(recommended: &[WordleEntity]) -> Vec<WordleEntity> {
+    recommended
+        .iter()
+        .filter(|entity| entity.is_candidate)
+        .cloned()
+        .sorted_by(|a, b| b.frequency.total_cmp(&a.frequency))
+        .collect()
+}
+
+/// Ranks the openers of the words of `word_length` letters starting with `first_char`: the `top`
+/// guesses with the most entropy, then the same guesses by the average number of guesses
+/// the solver needs after them, simulated with `simulation`. Simulating every word of the
+/// slice would take a game per answer for each of them, so the others are not simulated.
+/// # Errors
+/// Returns the `SolverError` of `PatternMatrix::new` if the patterns of the slice cannot be
+/// computed.
+#[allow(clippy::missing_panics_doc)]
+pub fn generate_table(
+    dictionary: &Dictionary,
+    word_length: usize,
+    first_char: &str,
+    top: usize,
+    simulation: &SimulationOptions,
+) -> Result<OpenerTable, SolverError> {
+    let matrix = PatternMatrix::new(dictionary, word_length, first_char)?;
+    let mut recommended = WordleSolver::retrieve_recommended_words_with_matrix(
+        &matrix,
+        &[],
+        &WordleSolver::SolverOptions::default(),
+    )
+    .expect("there is no step to reject");
+    ScoringMode::Entropy.sort(&mut recommended);
+    let mut possible = most_frequent_candidates(&recommended);
+    possible.truncate(top);
+    recommended.truncate(top);
+    let by_simulation = recommended
+        .iter()
+        .map(|opener| {
+            let options = SimulationOptions {
+                opener: Some(opener.word.clone()),
+                ..simulation.clone()
+            };
+            let report = WordleSimulation::simulate_with_matrix(&matrix, &options)
+                .expect("the opener is a word of the matrix");
+            SimulatedOpener {
+                word: opener.word.clone(),
+                average_guesses: report.average_guesses(),
+                failures: report.failures().len(),
+            }
+        })
+        .sorted_by(|a, b| {
+            a.average_guesses
+                .total_cmp(&b.average_guesses)
+                .then(a.failures.cmp(&b.failures))
+        })
+        .collect();
+    Ok(OpenerTable {
+        word_length,
+        first_char: first_char.to_string(),
+        words: matrix.words().len(),
+        digest: slice_digest(dictionary, word_length, first_char),
+        by_entropy: recommended,
+        by_simulation,
+        possible,
+    })
+}
+
+/// Generates the opener table of every word length, without a first letter and for each first
+/// letter of the dictionary. The lengths with too many words for a matrix have no table
+/// without a first letter. `progress` is called with each table once generated.
+/// # Errors
+/// Returns the `SolverError` of `PatternMatrix::new` if the patterns of a slice cannot be
+/// computed.
+pub fn generate(
+    dictionary: &Dictionary,
+    top: usize,
+    simulation: &SimulationOptions,
+    mut progress: impl FnMut(&OpenerTable),
+) -> Result<Openers, SolverError> {
+    let mut tables = Vec::new();
+    for word_length in dictionary.word_lengths() {
+        let first_chars = dictionary
+            .words(word_length, "")
+            .iter()
+            .filter_map(|(word, _)| word.chars().next())
+            .dedup()
+            .map(String::from);
+        for first_char in std::iter::once(String::new()).chain(first_chars) {
+            let table = match generate_table(dictionary, word_length, &first_char, top, simulation)
+            {
+                Err(SolverError::SliceTooLarge { .. }) if first_char.is_empty() => continue,
+                table => table?,
+            };
+            progress(&table);
+            tables.push(table);
+        }
+    }
+    Ok(Openers {
+        version: OPENERS_VERSION,
+        top,
+        tables,
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,123 @@
+use super::*;
+use crate::WordleFixtures::dictionary;
+use crate::WordleSolver::SolverOptions;
+
+#[test]
+fn Table_ranks_openers() {
+    let table = generate_table(&dictionary(), 5, "", 3, &SimulationOptions::default()).unwrap();
+    assert_eq!(table.words, 5);
+    assert_eq!(table.digest, slice_digest(&dictionary(), 5, ""));
+    assert_eq!(table.by_entropy.len(), 3);
+    let mut recommended = WordleSolver::retrieve_recommended_words(
+        &dictionary(),
+        &[],
+        5,
+        "",
+        &SolverOptions::default(),
+    )
+    .unwrap();
+    ScoringMode::Entropy.sort(&mut recommended);
+    assert_eq!(table.by_entropy, recommended[..3]);
+    assert!(table
+        .by_simulation
+        .iter()
+        .map(|opener| &opener.word)
+        .sorted()
+        .eq(table.by_entropy.iter().map(|opener| &opener.word).sorted()));
+    assert!(table
+        .by_simulation
+        .windows(2)
+        .all(|pair| pair[0].average_guesses <= pair[1].average_guesses));
+    assert_eq!(table.possible.len(), 3);
+    assert!(table.possible.iter().all(|we| we.is_candidate));
+}
+
+#[test]
+fn Digest_follows_the_words() {
+    let digest = slice_digest(&dictionary(), 5, "t");
+    assert_eq!(digest.len(), 16);
+    assert_eq!(digest, slice_digest(&dictionary(), 5, "t"));
+    assert_ne!(digest, slice_digest(&dictionary(), 5, ""));
+    let renamed = Dictionary::from_words([("tarie", 1.0), ("tarin", 1.0)]);
+    assert_ne!(digest, slice_digest(&renamed, 5, "t"));
+    let reweighted = Dictionary::from_words([("tarie", 1.0), ("taris", 2.0)]);
+    assert_ne!(digest, slice_digest(&reweighted, 5, "t"));
+}
+
+#[test]
+fn Openers_json_round_trip() {
+    let openers = generate(&dictionary(), 2, &SimulationOptions::default(), |_| {}).unwrap();
+    assert_eq!(openers.version, OPENERS_VERSION);
+    assert_eq!(
+        openers
+            .tables
+            .iter()
+            .map(|table| (table.word_length, table.first_char.as_str()))
+            .collect_vec(),
+        [(5, ""), (5, "b"), (5, "c"), (5, "p"), (5, "t")]
+    );
+    let json = openers.to_json();
+    assert_eq!(json.lines().count(), 7);
+    assert_eq!(Openers::from_json(&json), Ok(openers));
+    assert_eq!(
+        Openers::from_json("{\"version\":0,\"top\":5,\"tables\":[]}"),
+        Err(OpenersError::Version {
+            found: 0,
+            expected: OPENERS_VERSION
+        })
+    );
+    assert!(matches!(
+        Openers::from_json("{}"),
+        Err(OpenersError::Json(_))
+    ));
+}
+
+#[test]
+fn Lexique_openers_are_served() {
+    let lexique = Dictionary::lexique();
+    let openers = Openers::lexique();
+    assert_eq!(openers.version, OPENERS_VERSION);
+    let served = opening_words(lexique, 5, "t", &SolverOptions::default()).unwrap();
+    assert_eq!(served[0].word, "tarie");
+    assert_eq!(served.len(), openers.top);
+    // Every table matches the embedded dictionary.
+    for table in &openers.tables {
+        assert_eq!(
+            table.digest,
+            slice_digest(lexique, table.word_length, &table.first_char)
+        );
+    }
+    assert!(opening_words(lexique, 5, "", &SolverOptions::default()).is_some());
+    let sigmoid = SolverOptions {
+        prior: Prior::DEFAULT_SIGMOID,
+        ..SolverOptions::default()
+    };
+    assert_eq!(opening_words(lexique, 5, "t", &sigmoid), None);
+    assert_eq!(
+        opening_words(&dictionary(), 5, "t", &SolverOptions::default()),
+        None
+    );
+}
+
+#[test]
+fn Recommendations_serve_the_openers() {
+    let options = SolverOptions::default();
+    let served = recommendations(Dictionary::lexique(), &[], 5, "t", &options, 5).unwrap();
+    assert!(served.precomputed);
+    let computed = recommendations(Dictionary::lexique(), &[], 5, "t", &options, 6).unwrap();
+    assert!(!computed.precomputed);
+    assert_eq!(
+        scored_recommendations(Dictionary::lexique(), &[], 5, "t", &options).unwrap(),
+        computed
+    );
+    assert_eq!(computed.recommended.len(), computed.recommended_count);
+    assert_eq!(served.recommended, computed.recommended[..5]);
+    assert_eq!(served.possible, computed.possible[..5]);
+    assert_eq!(served.recommended_count, computed.recommended_count);
+    assert_eq!(served.possible_count, computed.possible_count);
+
+    let steps = [("tarie".to_string(), "20000".to_string())];
+    let after = recommendations(Dictionary::lexique(), &steps, 5, "t", &options, 5).unwrap();
+    assert!(!after.precomputed);
+    assert!(after.possible_count < served.possible_count);
+}
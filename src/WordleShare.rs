@@ -0,0 +1,149 @@
+use crate::WordleSolver::{self, Pattern, SolverError};
+use itertools::Itertools;
+use std::fmt;
+
+/// Emoji sets of the shared result grids.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum EmojiStyle {
+    /// 🟩 correct, 🟨 misplaced, ⬛ incorrect (⬜ with the light theme).
+    #[default]
+    Wordle,
+    /// 🟥 correct, 🟡 misplaced, 🟦 incorrect.
+    Sutom,
+}
+
+impl EmojiStyle {
+    #[must_use]
+    pub fn emoji(self, pattern: Pattern) -> char {
+        match (self, pattern) {
+            (EmojiStyle::Wordle, Pattern::Correct) => '🟩',
+            (EmojiStyle::Wordle, Pattern::Misplaced) => '🟨',
+            (EmojiStyle::Wordle, Pattern::Incorrect) => '⬛',
+            (EmojiStyle::Sutom, Pattern::Correct) => '🟥',
+            (EmojiStyle::Sutom, Pattern::Misplaced) => '🟡',
+            (EmojiStyle::Sutom, Pattern::Incorrect) => '🟦',
+        }
+    }
+}
+
+/// Pattern of an emoji of either style, `None` for anything else.
+fn parse_emoji(emoji: char) -> Option<Pattern> {
+    match emoji {
+        '🟩' | '🟥' => Some(Pattern::Correct),
+        '🟨' | '🟡' => Some(Pattern::Misplaced),
+        '⬛' | '⬜' | '🟦' => Some(Pattern::Incorrect),
+        _ => None,
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum ShareError {
+    RowCountMismatch {
+        rows: usize,
+        words: usize,
+    },
+    RowLengthMismatch {
+        row: usize,
+        length: usize,
+        expected: usize,
+    },
+    InvalidStep(SolverError),
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RowCountMismatch { rows, words } => write!(
+                f,
+                "The grid has {rows} rows but {words} guessed words were given"
+            ),
+            Self::RowLengthMismatch {
+                row,
+                length,
+                expected,
+            } => write!(
+                f,
+                "Row {row} of the grid has {length} tiles but the first row has {expected}"
+            ),
+            Self::InvalidStep(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ShareError {}
+
+impl From<SolverError> for ShareError {
+    fn from(error: SolverError) -> Self {
+        Self::InvalidStep(error)
+    }
+}
+
+/// Reads a pasted result grid of either style along with the guessed words, in order,
+/// into the steps `retrieve_recommended_words` takes. Lines holding anything else than
+/// emoji, like the "Wordle 1 234 4/6" header, are skipped.
+/// # Errors
+/// Returns `ShareError::RowCountMismatch` if there are not as many rows as words,
+/// `ShareError::RowLengthMismatch` if the rows do not all have as many tiles, and
+/// `ShareError::InvalidStep` if a word and its row do not make a valid step.
+pub fn parse_emoji_grid(grid: &str, words: &[String]) -> Result<Vec<(String, String)>, ShareError> {
+    let rows = grid
+        .lines()
+        .map(|line| {
+            line.chars()
+                // Skips the variation selector some platforms append to ⬛ and ⬜.
+                .filter(|c| !c.is_whitespace() && *c != '\u{fe0f}')
+                .map(parse_emoji)
+                .collect::<Option<Vec<_>>>()
+        })
+        .filter_map(|row| row.filter(|row| !row.is_empty()))
+        .collect_vec();
+    if rows.len() != words.len() {
+        return Err(ShareError::RowCountMismatch {
+            rows: rows.len(),
+            words: words.len(),
+        });
+    }
+    if let Some((row, length)) = rows
+        .iter()
+        .map(Vec::len)
+        .enumerate()
+        .find(|&(_, length)| length != rows[0].len())
+    {
+        return Err(ShareError::RowLengthMismatch {
+            row: row + 1,
+            length,
+            expected: rows[0].len(),
+        });
+    }
+    words
+        .iter()
+        .zip(rows)
+        .map(|(word, row)| {
+            let word = word.trim().to_lowercase();
+            let pattern = WordleSolver::format_pattern(&row);
+            WordleSolver::parse_step(&word, &pattern, word.chars().count())?;
+            Ok((word, pattern))
+        })
+        .collect()
+}
+
+/// Renders the patterns of the steps as a result grid, one row per step.
+/// # Errors
+/// Returns `SolverError::InvalidPatternChar` if a pattern has something else than 0, 1 or 2.
+pub fn to_emoji_grid(steps: &[(String, String)], style: EmojiStyle) -> Result<String, SolverError> {
+    Ok(steps
+        .iter()
+        .map(|(_, pattern)| {
+            WordleSolver::parse_pattern(pattern).map(|pattern| {
+                pattern
+                    .into_iter()
+                    .map(|tile| style.emoji(tile))
+                    .collect::<String>()
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests;
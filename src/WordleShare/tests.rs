@@ -0,0 +1,72 @@
+use super::*;
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn Parse_wordle_grid() {
+    let grid = "Wordle 1 234 3/6\n\n⬛🟨⬛⬛🟩\n⬜️🟩🟩⬛️🟩\n🟩🟩🟩🟩🟩\n";
+    let steps = parse_emoji_grid(grid, &words(&["crane", "Tarie", "taire"])).unwrap();
+    assert_eq!(
+        steps,
+        vec![
+            ("crane".to_string(), "01002".to_string()),
+            ("tarie".to_string(), "02202".to_string()),
+            ("taire".to_string(), "22222".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn Parse_sutom_grid() {
+    let grid = "SUTOM #123 2/6\n\n🟥🟦🟡🟦🟦🟦\n🟥🟥🟥🟥🟥🟥";
+    let steps = parse_emoji_grid(grid, &words(&["tartes", "tomate"])).unwrap();
+    assert_eq!(steps[0].1, "201000");
+    assert_eq!(steps[1].1, "222222");
+}
+
+#[test]
+fn Parse_grid_errors() {
+    assert_eq!(
+        parse_emoji_grid("🟩🟩🟩🟩🟩", &words(&["tarie", "taris"])),
+        Err(ShareError::RowCountMismatch { rows: 1, words: 2 })
+    );
+    assert_eq!(
+        parse_emoji_grid("🟩⬛⬛⬛⬛\n🟩🟩🟩🟩🟩🟩", &words(&["tarie", "tartes"])),
+        Err(ShareError::RowLengthMismatch {
+            row: 2,
+            length: 6,
+            expected: 5
+        })
+    );
+    assert_eq!(
+        parse_emoji_grid("🟩🟩🟩🟩", &words(&["tarie"])),
+        Err(ShareError::InvalidStep(
+            SolverError::PatternLengthMismatch {
+                pattern: "2222".to_string(),
+                word_length: 5
+            }
+        ))
+    );
+}
+
+#[test]
+fn Emoji_grid_round_trip() {
+    let steps = vec![
+        ("crane".to_string(), "01002".to_string()),
+        ("taire".to_string(), "22222".to_string()),
+    ];
+    let grid = to_emoji_grid(&steps, EmojiStyle::Wordle).unwrap();
+    assert_eq!(grid, "⬛🟨⬛⬛🟩\n🟩🟩🟩🟩🟩");
+    let words = steps.iter().map(|(word, _)| word.clone()).collect_vec();
+    assert_eq!(parse_emoji_grid(&grid, &words).unwrap(), steps);
+    let sutom = to_emoji_grid(&steps, EmojiStyle::Sutom).unwrap();
+    assert_eq!(sutom, "🟦🟡🟦🟦🟥\n🟥🟥🟥🟥🟥");
+    assert_eq!(parse_emoji_grid(&sutom, &words).unwrap(), steps);
+    assert!(to_emoji_grid(
+        &[("crane".to_string(), "0x002".to_string())],
+        EmojiStyle::Wordle
+    )
+    .is_err());
+}
@@ -0,0 +1,189 @@
+use crate::WordleDictionary::Dictionary;
+use crate::WordleSolver::{self, PatternMatrix, SolverError, SolverOptions};
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Number of guesses Wordle allows before the game is lost.
+pub const DEFAULT_MAX_GUESSES: usize = 6;
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct SimulationOptions {
+    pub solver: SolverOptions,
+    /// Games needing more guesses than this are reported as failures.
+    pub max_guesses: usize,
+    /// Number of answers drawn at random, every word of the slice is played when `None`.
+    pub sample: Option<usize>,
+    /// Seed of the answer sampling, so that runs can be compared.
+    pub seed: u64,
+    /// First guess of every game, the solver's suggestion when `None`.
+    pub opener: Option<String>,
+}
+
+impl Default for SimulationOptions {
+    fn default() -> Self {
+        Self {
+            solver: SolverOptions::default(),
+            max_guesses: DEFAULT_MAX_GUESSES,
+            sample: None,
+            seed: 0,
+            opener: None,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct GameResult {
+    pub answer: String,
+    /// Words played in order, the last one being the answer.
+    pub guesses: Vec<String>,
+}
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct SimulationReport {
+    pub max_guesses: usize,
+    pub games: Vec<GameResult>,
+}
+
+impl SimulationReport {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average_guesses(&self) -> f32 {
+        let total = self
+            .games
+            .iter()
+            .map(|game| game.guesses.len())
+            .sum::<usize>();
+        total as f32 / self.games.len().max(1) as f32
+    }
+
+    /// Number of games won for each number of guesses.
+    #[must_use]
+    pub fn distribution(&self) -> BTreeMap<usize, usize> {
+        self.games
+            .iter()
+            .map(|game| game.guesses.len())
+            .counts()
+            .into_iter()
+            .collect()
+    }
+
+    /// Games needing more than `max_guesses` guesses.
+    #[must_use]
+    pub fn failures(&self) -> Vec<&GameResult> {
+        self.games
+            .iter()
+            .filter(|game| game.guesses.len() > self.max_guesses)
+            .collect()
+    }
+
+    /// The `count` games needing the most guesses, longest first.
+    #[must_use]
+    pub fn worst_games(&self, count: usize) -> Vec<&GameResult> {
+        self.games
+            .iter()
+            .sorted_by(|a, b| b.guesses.len().cmp(&a.guesses.len()))
+            .take(count)
+            .collect()
+    }
+}
+
+/// Plays the solver's suggested guess, see `WordleSolver::suggested_guess`, against every answer,
+/// see `Dictionary::is_answer`, of `word_length` letters starting with `first_char` (or a sample of them) until it is found.
+/// Games going through the same steps get the same guess, so each position is only solved once.
+/// # Errors
+/// Returns the `SolverError` of `PatternMatrix::new` if the patterns of the slice cannot be
+/// computed, or `SolverError::UnknownWord` if `options.opener` is not a word of the slice.
+pub fn simulate(
+    dictionary: &Dictionary,
+    word_length: usize,
+    first_char: &str,
+    options: &SimulationOptions,
+) -> Result<SimulationReport, SolverError> {
+    simulate_with_matrix(
+        &PatternMatrix::new(dictionary, word_length, first_char)?,
+        options,
+    )
+}
+
+/// Same as `simulate`, with the patterns of the slice already computed, e.g. to compare openers.
+/// # Errors
+/// Returns `SolverError::UnknownWord` if `options.opener` is not a word of the slice.
+pub fn simulate_with_matrix(
+    matrix: &PatternMatrix,
+    options: &SimulationOptions,
+) -> Result<SimulationReport, SolverError> {
+    let mut answers = (0..matrix.words().len())
+        .filter(|&word| matrix.is_answer(word))
+        .collect_vec();
+    if let Some(sample) = options.sample {
+        let mut rng = StdRng::seed_from_u64(options.seed);
+        answers.shuffle(&mut rng);
+        answers.truncate(sample);
+        answers.sort_unstable();
+    }
+    let mut guesses_by_steps = HashMap::new();
+    if let Some(opener) = &options.opener {
+        let index = matrix
+            .words()
+            .binary_search_by(|(word, _)| word.cmp(opener))
+            .map_err(|_| SolverError::UnknownWord {
+                word: opener.clone(),
+            })?;
+        guesses_by_steps.insert(Vec::new(), Some(index));
+    }
+    let games = answers
+        .into_iter()
+        .map(|answer| play_game(matrix, answer, &options.solver, &mut guesses_by_steps))
+        .collect();
+    Ok(SimulationReport {
+        max_guesses: options.max_guesses,
+        games,
+    })
+}
+
+fn play_game(
+    matrix: &PatternMatrix,
+    answer: usize,
+    options: &SolverOptions,
+    guesses_by_steps: &mut HashMap<Vec<(String, String)>, Option<usize>>,
+) -> GameResult {
+    let words = matrix.words();
+    let mut steps = Vec::new();
+    let mut guesses = Vec::new();
+    loop {
+        let guess = if let Some(&guess) = guesses_by_steps.get(&steps) {
+            guess
+        } else {
+            let mut recommended =
+                WordleSolver::retrieve_recommended_words_with_matrix(matrix, &steps, options)
+                    .expect("steps are words of the matrix with the patterns it gave");
+            options.scoring.sort(&mut recommended);
+            let guess = WordleSolver::suggested_guess(&recommended)
+                .and_then(|we| words.iter().position(|(word, _)| *word == we.word));
+            guesses_by_steps.insert(steps.clone(), guess);
+            guess
+        };
+        // The answer matches every step, so there always is a candidate to suggest.
+        let guess = guess.expect("the answer remains a candidate");
+        guesses.push(words[guess].0.clone());
+        if guess == answer {
+            return GameResult {
+                answer: words[answer].0.clone(),
+                guesses,
+            };
+        }
+        let pattern =
+            WordleSolver::decode_pattern(matrix.code(guess, answer), matrix.word_length());
+        steps.push((
+            words[guess].0.clone(),
+            WordleSolver::format_pattern(&pattern),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests;
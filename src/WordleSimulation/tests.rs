@@ -0,0 +1,127 @@
+use super::*;
+use crate::WordleDictionary::{AnswerFilter, DictionaryFormat};
+use crate::WordleFixtures;
+
+#[test]
+fn Simulation_small_dictionary() {
+    let dictionary = WordleFixtures::dictionary_without_blocu();
+    let report = simulate(&dictionary, 5, "", &SimulationOptions::default()).unwrap();
+    assert_eq!(report.games.len(), 4);
+    for game in &report.games {
+        assert_eq!(game.guesses.last(), Some(&game.answer));
+    }
+    assert_eq!(report.distribution().values().sum::<usize>(), 4);
+    assert!(report.failures().is_empty());
+    assert_eq!(
+        report.worst_games(1)[0].guesses.len(),
+        *report.distribution().keys().last().unwrap()
+    );
+}
+
+#[test]
+fn Simulation_sample_is_seeded() {
+    let options = SimulationOptions {
+        sample: Some(20),
+        seed: 42,
+        ..SimulationOptions::default()
+    };
+    let first = simulate(Dictionary::lexique(), 5, "t", &options).unwrap();
+    let second = simulate(Dictionary::lexique(), 5, "t", &options).unwrap();
+    assert_eq!(first.games.len(), 20);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn Simulation_opener() {
+    let dictionary = WordleFixtures::dictionary_without_blocu();
+    let options = SimulationOptions {
+        opener: Some("poele".to_string()),
+        ..SimulationOptions::default()
+    };
+    let report = simulate(&dictionary, 5, "", &options).unwrap();
+    assert!(report.games.iter().all(|game| game.guesses[0] == "poele"));
+    assert_eq!(
+        simulate(
+            &dictionary,
+            5,
+            "",
+            &SimulationOptions {
+                opener: Some("blocu".to_string()),
+                ..SimulationOptions::default()
+            }
+        ),
+        Err(SolverError::UnknownWord {
+            word: "blocu".to_string()
+        })
+    );
+}
+
+//Plays every 5 letters word starting with t, the quality benchmark of the solver.
+#[test]
+fn StressTests_simulation() {
+    let report = simulate(Dictionary::lexique(), 5, "t", &SimulationOptions::default()).unwrap();
+    assert_eq!(
+        report.games.len(),
+        Dictionary::lexique().words(5, "t").len()
+    );
+    assert!(report
+        .games
+        .iter()
+        .all(|game| game.guesses[0] == "tarie" || game.answer == "tarie"));
+    assert!(report.average_guesses() < 4.0);
+    assert!(report.failures().len() <= report.games.len() / 100);
+}
+
+#[test]
+fn StressTests_simulation_hard_mode() {
+    let options = SimulationOptions {
+        solver: SolverOptions {
+            hard_mode: true,
+            ..SolverOptions::default()
+        },
+        ..SimulationOptions::default()
+    };
+    let hard = simulate(Dictionary::lexique(), 5, "t", &options).unwrap();
+    let easy = simulate(Dictionary::lexique(), 5, "t", &SimulationOptions::default()).unwrap();
+    assert_eq!(hard.games.len(), easy.games.len());
+    for game in &hard.games {
+        // Each guess matches the patterns the previous ones got against the answer.
+        for (turn, guess) in game.guesses.iter().enumerate() {
+            assert!(game.guesses[..turn].iter().all(|previous| {
+                let pattern = WordleSolver::get_pattern(previous, &game.answer);
+                WordleSolver::Rule::new(previous, &pattern).Is_Word_Conform(guess)
+            }));
+        }
+    }
+    assert!(hard.average_guesses() < 4.5);
+}
+
+#[test]
+fn Simulation_restricted_answers() {
+    let content = "1_ortho;3_lemme;4_cgram;10_freqlivres
+tarie;tarir;VER;1
+taris;tarir;VER;1
+tarot;tarot;NOM;1
+poele;poele;NOM;1
+crise;crise;NOM;1
+";
+    let mut dictionary = Dictionary::from_csv(content, &DictionaryFormat::lexique()).unwrap();
+    dictionary
+        .restrict_answers(&AnswerFilter {
+            categories: vec!["NOM".to_string()],
+            ..AnswerFilter::default()
+        })
+        .unwrap();
+    let report = simulate(&dictionary, 5, "", &SimulationOptions::default()).unwrap();
+    assert_eq!(
+        report
+            .games
+            .iter()
+            .map(|game| game.answer.as_str())
+            .collect_vec(),
+        ["crise", "poele", "tarot"]
+    );
+    for game in &report.games {
+        assert_eq!(game.guesses.last(), Some(&game.answer));
+    }
+}
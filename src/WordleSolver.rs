@@ -1,47 +1,382 @@
+use crate::WordleDictionary::{Dictionary, Normalization};
 use core::cmp;
-use deunicode::deunicode;
 use itertools::Itertools;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(PartialEq,Clone)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub struct WordleEntity {
     pub word: String,
     pub frequency: f32,
     pub entropy: f32,
     pub is_candidate: bool,
+    /// Expected information after this guess and the best follow-up, see `ScoringMode::TwoStep`.
+    pub two_step_entropy: Option<f32>,
+    /// Expected number of candidates left after this guess and the best follow-up.
+    pub two_step_remaining: Option<f32>,
+    /// Expected number of guesses to find the answer when playing this word next, see `ScoringMode::ExpectedGuesses`.
+    pub expected_guesses: f32,
+    /// Probability of being the answer according to `SolverOptions::prior`, 0 for non candidates.
+    pub probability: f32,
+    /// Number of candidates left in the worst case, see `ScoringMode::Minimax`.
+    pub largest_bucket: usize,
+    /// Expected number of candidates left, see `ScoringMode::ExpectedBucket`.
+    pub expected_bucket: f32,
 }
 
-#[derive(PartialEq, Clone, Debug, Hash, Eq)]
+/// How recommended words are ranked.
+#[derive(PartialEq, Clone, Copy, Debug, Default, Serialize)]
+pub enum ScoringMode {
+    /// Expected information of the guess alone.
+    #[default]
+    Entropy,
+    /// Expected information of the guess plus the best follow-up guess for each of its
+    /// pattern buckets, computed for the `SolverOptions::lookahead_top` best entropy guesses.
+    TwoStep,
+    /// Expected number of guesses to finish the game, favouring candidates that may win
+    /// right away over slightly more informative words that cannot be the answer.
+    ExpectedGuesses,
+    /// Number of candidates left by the least informative pattern, as an adversarial
+    /// host picking the pattern after the guess would leave them, candidates first on ties.
+    Minimax,
+    /// Expected number of candidates left after the guess, the size of each pattern
+    /// bucket weighted by its probability.
+    ExpectedBucket,
+}
+
+impl ScoringMode {
+    pub const ALL: [ScoringMode; 5] = [
+        ScoringMode::Entropy,
+        ScoringMode::TwoStep,
+        ScoringMode::ExpectedGuesses,
+        ScoringMode::Minimax,
+        ScoringMode::ExpectedBucket,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            ScoringMode::Entropy => "entropy",
+            ScoringMode::TwoStep => "two-step",
+            ScoringMode::ExpectedGuesses => "expected-guesses",
+            ScoringMode::Minimax => "minimax",
+            ScoringMode::ExpectedBucket => "expected-bucket",
+        }
+    }
+
+    /// Sorts `words` from the best to the worst guess.
+    pub fn sort(self, words: &mut [WordleEntity]) {
+        match self {
+            ScoringMode::Entropy => words.sort_by(|a, b| b.entropy.total_cmp(&a.entropy)),
+            ScoringMode::TwoStep => words.sort_by(|a, b| {
+                match (b.two_step_entropy, a.two_step_entropy) {
+                    (Some(b), Some(a)) => b.total_cmp(&a),
+                    (b, a) => b.is_some().cmp(&a.is_some()),
+                }
+                .then(b.entropy.total_cmp(&a.entropy))
+            }),
+            ScoringMode::ExpectedGuesses => words.sort_by(|a, b| {
+                a.expected_guesses
+                    .total_cmp(&b.expected_guesses)
+                    .then(b.entropy.total_cmp(&a.entropy))
+            }),
+            ScoringMode::Minimax => words.sort_by(|a, b| {
+                a.largest_bucket
+                    .cmp(&b.largest_bucket)
+                    .then(b.is_candidate.cmp(&a.is_candidate))
+                    .then(b.entropy.total_cmp(&a.entropy))
+            }),
+            ScoringMode::ExpectedBucket => words.sort_by(|a, b| {
+                a.expected_bucket
+                    .total_cmp(&b.expected_bucket)
+                    .then(b.entropy.total_cmp(&a.entropy))
+            }),
+        }
+    }
+}
+
+impl FromStr for ScoringMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ScoringMode::ALL
+            .into_iter()
+            .find(|mode| mode.name() == s)
+            .ok_or_else(|| format!("unknown scoring mode '{s}'"))
+    }
+}
+
+/// How likely each candidate is to be the answer, given its frequency.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum Prior {
+    /// Every candidate is as likely.
+    #[default]
+    Uniform,
+    /// Words ranked by decreasing frequency are weighted by a sigmoid of their relative rank:
+    /// words ranked before `midpoint` (between 0 and 1) are likely answers, the ones after it
+    /// fade out, all the faster as `steepness` is large.
+    Sigmoid { midpoint: f32, steepness: f32 },
+}
+
+impl Prior {
+    pub const DEFAULT_SIGMOID: Prior = Prior::Sigmoid {
+        midpoint: 0.4,
+        steepness: 12.0,
+    };
+
+    /// Unnormalized weight of every word of a dictionary slice.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn weights(self, words: &[(String, f32)]) -> Vec<f32> {
+        match self {
+            Prior::Uniform => vec![1.0; words.len()],
+            Prior::Sigmoid {
+                midpoint,
+                steepness,
+            } => {
+                let mut frequencies = words.iter().map(|(_, freq)| *freq).collect_vec();
+                frequencies.sort_by(|a, b| b.total_cmp(a));
+                let count = words.len() as f32;
+                words
+                    .iter()
+                    .map(|(_, freq)| {
+                        let rank = frequencies.partition_point(|other| other > freq) as f32;
+                        1.0 / (1.0 + (steepness * (rank / count - midpoint)).exp())
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Prior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Prior::Uniform => write!(f, "uniform"),
+            Prior::Sigmoid {
+                midpoint,
+                steepness,
+            } => write!(f, "sigmoid:{midpoint}:{steepness}"),
+        }
+    }
+}
+
+impl FromStr for Prior {
+    type Err = String;
+
+    /// Parses `uniform`, `sigmoid` or `sigmoid:<midpoint>:<steepness>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |value: &str| {
+            value
+                .parse::<f32>()
+                .map_err(|_| format!("invalid prior parameter '{value}'"))
+        };
+        match s.split(':').collect_vec().as_slice() {
+            ["uniform"] => Ok(Prior::Uniform),
+            ["sigmoid"] => Ok(Prior::DEFAULT_SIGMOID),
+            ["sigmoid", midpoint, steepness] => {
+                let (midpoint, steepness) = (parse(midpoint)?, parse(steepness)?);
+                if !(0.0..=1.0).contains(&midpoint) {
+                    return Err(format!(
+                        "invalid prior midpoint '{midpoint}', expected a rank between 0 and 1"
+                    ));
+                }
+                if !steepness.is_finite() || steepness < 0.0 {
+                    return Err(format!(
+                        "invalid prior steepness '{steepness}', expected a finite positive number"
+                    ));
+                }
+                Ok(Prior::Sigmoid {
+                    midpoint,
+                    steepness,
+                })
+            }
+            _ => Err(format!("unknown prior '{s}'")),
+        }
+    }
+}
+
+pub const DEFAULT_LOOKAHEAD_TOP: usize = 10;
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct SolverOptions {
+    pub scoring: ScoringMode,
+    /// Number of best entropy guesses explored by `ScoringMode::TwoStep`.
+    pub lookahead_top: usize,
+    pub prior: Prior,
+    /// Only guess words satisfying every step, as Wordle's hard mode requires.
+    pub hard_mode: bool,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            scoring: ScoringMode::default(),
+            lookahead_top: DEFAULT_LOOKAHEAD_TOP,
+            prior: Prior::default(),
+            hard_mode: false,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, Hash, Eq)]
 pub enum Pattern {
     Incorrect,
     Misplaced,
     Correct,
 }
 
+#[derive(PartialEq, Clone, Debug, Eq)]
+pub enum SolverError {
+    InvalidPatternChar {
+        pattern: String,
+        character: char,
+    },
+    PatternLengthMismatch {
+        pattern: String,
+        word_length: usize,
+    },
+    WordLengthMismatch {
+        word: String,
+        word_length: usize,
+    },
+    NonAlphabeticGuess {
+        word: String,
+    },
+    UnknownWord {
+        word: String,
+    },
+    /// Patterns of words longer than `MAX_ENCODED_LENGTH` letters do not fit a `PatternCode`.
+    PatternTooLong {
+        word_length: usize,
+    },
+    /// The patterns of the slice would take more than `MAX_MATRIX_BYTES`.
+    SliceTooLarge {
+        words: usize,
+        bytes: usize,
+    },
+    /// No answer has the word length and the first letter.
+    NoAnswer {
+        word_length: usize,
+        first_char: String,
+    },
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPatternChar { pattern, character } => write!(
+                f,
+                "Pattern '{pattern}' contains '{character}', only 0, 1 or 2 are allowed"
+            ),
+            Self::PatternLengthMismatch {
+                pattern,
+                word_length,
+            } => write!(
+                f,
+                "Pattern '{pattern}' should be {word_length} characters long"
+            ),
+            Self::WordLengthMismatch { word, word_length } => {
+                write!(f, "Word '{word}' should be {word_length} letters long")
+            }
+            Self::NonAlphabeticGuess { word } => {
+                write!(f, "Word '{word}' should only contain letters")
+            }
+            Self::UnknownWord { word } => write!(f, "Word '{word}' is not in the dictionary"),
+            Self::PatternTooLong { word_length } => write!(
+                f,
+                "Words of {word_length} letters are longer than the {MAX_ENCODED_LENGTH} letters \
+                 patterns can be encoded for"
+            ),
+            Self::SliceTooLarge { words, bytes } => write!(
+                f,
+                "The patterns of {words} words would take {} MiB, more than the {} MiB allowed, \
+                 give a first letter",
+                bytes >> 20,
+                MAX_MATRIX_BYTES >> 20
+            ),
+            Self::NoAnswer {
+                word_length,
+                first_char,
+            } => write!(
+                f,
+                "No answer has {word_length} letters and starts with '{first_char}'"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
+
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
 pub fn get_uniform_entropy(count: i32) -> f32 {
     let probabilities = (0..count).map(|_| 1.0 / (count as f32)).collect_vec();
     get_entropy(&probabilities)
 }
 
+/// Weights of `estimated_guesses`, fitted by least squares on the 18 905 guesses played by
+/// `rustle-cli simulate --length 5` (every 5 letter word of Lexique, entropy ranking, uniform
+/// prior): the guesses left, the current one excluded, against the bits of the candidates
+/// before each guess. Slices with a first letter fit between 0.71 and 0.84 for the logarithm.
+pub const ESTIMATE_LOG_WEIGHT: f32 = 0.72;
+pub const ESTIMATE_LINEAR_WEIGHT: f32 = 0.07;
+
+/// Rough number of guesses still needed to find the answer among candidates
+/// carrying `remaining_entropy` bits, the last guess included.
+#[must_use]
+pub fn estimated_guesses(remaining_entropy: f32) -> f32 {
+    let remaining_entropy = remaining_entropy.max(0.0);
+    1.0 + ESTIMATE_LOG_WEIGHT * remaining_entropy.ln_1p()
+        + ESTIMATE_LINEAR_WEIGHT * remaining_entropy
+}
+
+/// Expected number of guesses when playing a word that is the answer with `probability`
+/// and otherwise leaves `remaining_entropy - entropy` bits to find.
+fn expected_guesses(probability: f32, entropy: f32, remaining_entropy: f32) -> f32 {
+    probability + (1.0 - probability) * (1.0 + estimated_guesses(remaining_entropy - entropy))
+}
+
+/// Outcomes that cannot happen carry no information, `0 * log2(0)` counts for 0.
 fn get_entropy(probabilities: &[f32]) -> f32 {
     probabilities
         .iter()
+        .filter(|&&probability| probability > 0.0)
         .map(|probability| -probability * probability.log2())
         .sum()
 }
 
 //thanks https://github.com/conradludgate/wordle/blob/main/src/lib.rs
-fn get_pattern(actual_word: &str, target_word: &str) -> Vec<Pattern> {
-    let mut pattern_list = vec![Pattern::Incorrect; actual_word.len()];
+#[must_use]
+pub fn get_pattern(actual_word: &str, target_word: &str) -> Vec<Pattern> {
+    if actual_word.is_ascii() && target_word.is_ascii() {
+        match_letters(actual_word.as_bytes(), target_word.as_bytes())
+    } else {
+        // Letters kept with their accents span several bytes.
+        match_letters(
+            &actual_word.chars().collect_vec(),
+            &target_word.chars().collect_vec(),
+        )
+    }
+}
 
-    let actual_word = actual_word.as_bytes();
-    let mut target_word = target_word.as_bytes().to_owned();
+/// `T::default()` marks the letters of the target already matched, it is never a letter.
+#[inline]
+fn match_letters<T: Copy + Default + PartialEq>(
+    actual_word: &[T],
+    target_word: &[T],
+) -> Vec<Pattern> {
+    let mut pattern_list = vec![Pattern::Incorrect; actual_word.len()];
+    let mut target_word = target_word.to_owned();
 
     // find exact matches first
     for (i, &b) in actual_word.iter().enumerate() {
         if target_word[i] == b {
-            target_word[i] = 0; // letters only match once
+            target_word[i] = T::default(); // letters only match once
             pattern_list[i] = Pattern::Correct;
         }
     }
@@ -52,7 +387,7 @@ fn get_pattern(actual_word: &str, target_word: &str) -> Vec<Pattern> {
             continue;
         }
         if let Some(j) = target_word.iter().position(|&x| x == b) {
-            target_word[j] = 0; // letters only match once
+            target_word[j] = T::default(); // letters only match once
             pattern_list[i] = Pattern::Misplaced;
         }
     }
@@ -60,87 +395,643 @@ fn get_pattern(actual_word: &str, target_word: &str) -> Vec<Pattern> {
     pattern_list
 }
 
-fn parse_line(line: &str, word_length: usize, first_char: &str) -> Option<(String, f32)> {
-    let (word, freq) = line.split_once(';')?;
-    let decoded_word = deunicode(word);
-    let parsed_freq = freq.parse::<f32>().ok()?;
+/// A pattern packed as a base 3 number, the first letter being the least significant digit.
+/// The patterns of words longer than `MAX_ENCODED_LENGTH` letters do not fit and are rejected.
+pub type PatternCode = u64;
+pub const MAX_ENCODED_LENGTH: usize = 40;
 
-    if word.chars().all(char::is_alphabetic)
-        && word.chars().count() == word_length
-        && (first_char.chars().count() == 0 || decoded_word.starts_with(first_char.chars().next()?))
-    {
-        return Some((decoded_word, parsed_freq));
+impl Pattern {
+    fn digit(self) -> u8 {
+        match self {
+            Pattern::Incorrect => 0,
+            Pattern::Misplaced => 1,
+            Pattern::Correct => 2,
+        }
     }
-    None
 }
 
-pub fn retrieve_recommended_words(
-    patterns: &[(String, String)],
+#[must_use]
+pub fn encode_pattern(pattern: &[Pattern]) -> PatternCode {
+    pattern
+        .iter()
+        .rev()
+        .fold(0, |code, pat| code * 3 + PatternCode::from(pat.digit()))
+}
+
+#[must_use]
+pub fn decode_pattern(mut code: PatternCode, word_length: usize) -> Vec<Pattern> {
+    (0..word_length)
+        .map(|_| {
+            let pat = match code % 3 {
+                0 => Pattern::Incorrect,
+                1 => Pattern::Misplaced,
+                _ => Pattern::Correct,
+            };
+            code /= 3;
+            pat
+        })
+        .collect()
+}
+
+/// Same as `encode_pattern(&get_pattern(actual_word, target_word))`, without allocating
+/// for ASCII words.
+/// # Errors
+/// Returns `SolverError::PatternTooLong` if a word is longer than `MAX_ENCODED_LENGTH` letters.
+pub fn get_pattern_code(actual_word: &str, target_word: &str) -> Result<PatternCode, SolverError> {
+    let word_length = actual_word.chars().count().max(target_word.chars().count());
+    check_encoded_length(word_length)?;
+    Ok(pattern_code(actual_word, target_word))
+}
+
+fn check_encoded_length(word_length: usize) -> Result<(), SolverError> {
+    if word_length > MAX_ENCODED_LENGTH {
+        return Err(SolverError::PatternTooLong { word_length });
+    }
+    Ok(())
+}
+
+/// `get_pattern_code` for words already known to fit, see `check_encoded_length`.
+fn pattern_code(actual_word: &str, target_word: &str) -> PatternCode {
+    if actual_word.is_ascii() && target_word.is_ascii() {
+        code_letters(actual_word.as_bytes(), target_word.as_bytes())
+    } else {
+        code_letters(
+            &actual_word.chars().collect_vec(),
+            &target_word.chars().collect_vec(),
+        )
+    }
+}
+
+#[inline]
+fn code_letters<T: Copy + Default + PartialEq>(
+    actual_word: &[T],
+    target_word: &[T],
+) -> PatternCode {
+    let length = actual_word.len().min(target_word.len());
+    let mut target_buffer = [T::default(); MAX_ENCODED_LENGTH];
+    let target = &mut target_buffer[..length];
+    target.copy_from_slice(&target_word[..length]);
+    let mut digits = [0u8; MAX_ENCODED_LENGTH];
+
+    // find exact matches first
+    for (i, &b) in actual_word[..length].iter().enumerate() {
+        if target[i] == b {
+            target[i] = T::default(); // letters only match once
+            digits[i] = 2;
+        }
+    }
+
+    // now, find amber matches
+    for (i, &b) in actual_word[..length].iter().enumerate() {
+        if digits[i] != 0 {
+            continue;
+        }
+        if let Some(j) = target.iter().position(|&x| x == b) {
+            target[j] = T::default(); // letters only match once
+            digits[i] = 1;
+        }
+    }
+
+    digits[..length]
+        .iter()
+        .rev()
+        .fold(0, |code, &digit| code * 3 + PatternCode::from(digit))
+}
+
+/// # Errors
+/// Returns `SolverError::InvalidPatternChar` for any character other than 0, 1 or 2.
+pub fn parse_pattern(pattern: &str) -> Result<Vec<Pattern>, SolverError> {
+    pattern
+        .chars()
+        .map(|c| match c {
+            '0' => Ok(Pattern::Incorrect),
+            '1' => Ok(Pattern::Misplaced),
+            '2' => Ok(Pattern::Correct),
+            _ => Err(SolverError::InvalidPatternChar {
+                pattern: pattern.to_string(),
+                character: c,
+            }),
+        })
+        .collect()
+}
+
+/// Writes a pattern back in the 0/1/2 notation read by `parse_pattern`.
+#[must_use]
+pub fn format_pattern(pattern: &[Pattern]) -> String {
+    pattern
+        .iter()
+        .map(|&pattern| char::from(b'0' + pattern.digit()))
+        .collect()
+}
+
+/// Checks a guessed word and its pattern against the word length and builds the matching rule.
+/// # Errors
+/// Returns a `SolverError` when the word is not alphabetic, when the word or the pattern
+/// do not have `word_length` characters, or when the pattern contains something else than 0, 1 or 2.
+pub fn parse_step(word: &str, pattern: &str, word_length: usize) -> Result<Rule, SolverError> {
+    if !word.chars().all(char::is_alphabetic) {
+        return Err(SolverError::NonAlphabeticGuess {
+            word: word.to_string(),
+        });
+    }
+    if word.chars().count() != word_length {
+        return Err(SolverError::WordLengthMismatch {
+            word: word.to_string(),
+            word_length,
+        });
+    }
+    let parsed_pattern = parse_pattern(pattern)?;
+    if parsed_pattern.len() != word_length {
+        return Err(SolverError::PatternLengthMismatch {
+            pattern: pattern.to_string(),
+            word_length,
+        });
+    }
+    Ok(Rule::new(word, &parsed_pattern))
+}
+
+/// Reference implementation grouping `get_pattern` vectors, kept to benchmark the encoded path against.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn entropy_by_word(actual_word: &str, possible_words: &[String]) -> f32 {
+    let patterns = possible_words
+        .iter()
+        .map(|word| get_pattern(actual_word, word))
+        .collect_vec();
+    let probabilities = patterns
+        .iter()
+        .map(|t| (t, t))
+        .into_group_map()
+        .values()
+        .map(|group| group.len() as f32 / patterns.len() as f32)
+        .collect_vec();
+    get_entropy(&probabilities)
+}
+
+/// Entropy of `actual_word` over candidates paired with their probability, for a guess scored
+/// outside of the recommended words.
+#[must_use]
+pub fn weighted_entropy_by_word(actual_word: &str, possible_words: &[(String, f32)]) -> f32 {
+    let total = possible_words
+        .iter()
+        .map(|(_, probability)| probability)
+        .sum::<f32>();
+    let probabilities = possible_words
+        .iter()
+        .map(|(word, probability)| (get_pattern(actual_word, word), probability))
+        .into_group_map()
+        .values()
+        .map(|group| group.iter().copied().sum::<f32>() / total)
+        .collect_vec();
+    get_entropy(&probabilities)
+}
+
+/// Candidates sharing the same pattern: how many they are and their summed prior weight.
+struct Bucket {
+    size: usize,
+    weight: f32,
+}
+
+/// Groups identical pattern codes paired with the weight of their answer, sorting `codes` in place.
+fn buckets(codes: &mut [(PatternCode, f32)]) -> Vec<Bucket> {
+    codes.sort_unstable_by_key(|(code, _)| *code);
+    codes
+        .chunk_by(|a, b| a.0 == b.0)
+        .map(|group| Bucket {
+            size: group.len(),
+            weight: group.iter().map(|(_, weight)| weight).sum(),
+        })
+        .collect()
+}
+
+/// Bucket size expected from the bucket probabilities.
+#[allow(clippy::cast_precision_loss)]
+fn get_expected_bucket(buckets: &[Bucket]) -> f32 {
+    let total = buckets.iter().map(|bucket| bucket.weight).sum::<f32>();
+    buckets
+        .iter()
+        .map(|bucket| bucket.weight / total * bucket.size as f32)
+        .sum()
+}
+
+fn get_bucket_entropy(buckets: &[Bucket]) -> f32 {
+    let total = buckets.iter().map(|bucket| bucket.weight).sum::<f32>();
+    let probabilities = buckets
+        .iter()
+        .map(|bucket| bucket.weight / total)
+        .collect_vec();
+    get_entropy(&probabilities)
+}
+
+/// Largest pattern matrix built, about the size of the 7 letter words of Lexique.
+pub const MAX_MATRIX_BYTES: usize = 512 << 20;
+
+/// Pattern codes stored in the narrowest integer holding every pattern of the word length:
+/// a byte up to 5 letters, 3^5 = 243 patterns, two bytes up to 10 letters.
+enum MatrixCodes {
+    Byte(Vec<u8>),
+    Short(Vec<u16>),
+    Long(Vec<u32>),
+    Wide(Vec<PatternCode>),
+}
+
+impl MatrixCodes {
+    fn new(words: &[(String, f32)], word_length: usize) -> Result<Self, SolverError> {
+        check_encoded_length(word_length)?;
+        let width = match word_length {
+            0..=5 => 1,
+            6..=10 => 2,
+            11..=20 => 4,
+            _ => 8,
+        };
+        let bytes = words.len() * words.len() * width;
+        if bytes > MAX_MATRIX_BYTES {
+            return Err(SolverError::SliceTooLarge {
+                words: words.len(),
+                bytes,
+            });
+        }
+        Ok(match width {
+            1 => Self::Byte(fill_codes(words)),
+            2 => Self::Short(fill_codes(words)),
+            4 => Self::Long(fill_codes(words)),
+            _ => Self::Wide(fill_codes(words)),
+        })
+    }
+
+    fn get(&self, index: usize) -> PatternCode {
+        match self {
+            Self::Byte(codes) => PatternCode::from(codes[index]),
+            Self::Short(codes) => PatternCode::from(codes[index]),
+            Self::Long(codes) => PatternCode::from(codes[index]),
+            Self::Wide(codes) => codes[index],
+        }
+    }
+}
+
+fn fill_codes<C>(words: &[(String, f32)]) -> Vec<C>
+where
+    C: TryFrom<PatternCode> + Copy + Default + Send,
+    C::Error: fmt::Debug,
+{
+    let mut codes = vec![C::default(); words.len() * words.len()];
+    codes
+        .par_chunks_mut(words.len().max(1))
+        .zip(words.par_iter())
+        .for_each(|(row, (guess, _))| {
+            for (code, (answer, _)) in row.iter_mut().zip(words) {
+                *code = C::try_from(pattern_code(guess, answer))
+                    .expect("the width is chosen for the word length");
+            }
+        });
+    codes
+}
+
+/// Patterns of every guess against every answer of a dictionary slice, computed
+/// in parallel once so that the following turns only have to look them up.
+pub struct PatternMatrix {
     word_length: usize,
-    first_char: &str,
-) -> Vec<WordleEntity> {
-    
-    fn entropy_by_word(actual_word: &str, possible_words: &[String]) -> f32 {
-       
-        let patterns = possible_words
-            .iter()
-            .map(|word| get_pattern(actual_word, word))
-            .collect_vec();
-        let probabilities = patterns
+    words: Vec<(String, f32)>,
+    /// Whether each word may be the answer, see `Dictionary::is_answer`.
+    answers: Vec<bool>,
+    /// Normalization of the dictionary words, applied to the guesses.
+    normalization: Normalization,
+    codes: MatrixCodes,
+}
+
+impl PatternMatrix {
+    /// # Errors
+    /// Returns `SolverError::SliceTooLarge` if the patterns of the slice would take more than
+    /// `MAX_MATRIX_BYTES`, e.g. the 9 letter words without a first letter, or
+    /// `SolverError::PatternTooLong` if its words are longer than `MAX_ENCODED_LENGTH` letters.
+    pub fn new(
+        dictionary: &Dictionary,
+        word_length: usize,
+        first_char: &str,
+    ) -> Result<Self, SolverError> {
+        let words = dictionary.words(word_length, first_char).to_vec();
+        let codes = MatrixCodes::new(&words, word_length)?;
+        let answers = words
             .iter()
-            .map(|t| (t, t))
-            .into_group_map()
-            .values()
-            .map(|group| (group.len() as f32 / patterns.len() as f32))
-            .collect_vec();
-        get_entropy(&probabilities)
+            .map(|(word, _)| dictionary.is_answer(word))
+            .collect();
+        Ok(Self {
+            word_length,
+            words,
+            answers,
+            normalization: dictionary.normalization().clone(),
+            codes,
+        })
+    }
+
+    #[must_use]
+    pub fn word_length(&self) -> usize {
+        self.word_length
+    }
+
+    #[must_use]
+    pub fn words(&self) -> &[(String, f32)] {
+        &self.words
+    }
+
+    /// Whether `words()[word]` may be the answer.
+    #[must_use]
+    pub fn is_answer(&self, word: usize) -> bool {
+        self.answers[word]
+    }
+
+    /// Pattern obtained when guessing `words()[guess]` while the answer is `words()[answer]`.
+    #[must_use]
+    pub fn code(&self, guess: usize, answer: usize) -> PatternCode {
+        self.codes.get(guess * self.words.len() + answer)
     }
-    
-    let word_dictionary =  include_str!("Lexique381.csv")
-        .lines()
-        .filter_map(|line| parse_line(line, word_length, first_char))
-        .into_group_map_by(|(word, _)| word.clone())
+}
+
+/// Guesses are compared with the words of the dictionary as normalized by it.
+fn parse_steps(
+    patterns: &[(String, String)],
+    word_length: usize,
+    normalization: &Normalization,
+) -> Result<Vec<Rule>, SolverError> {
+    patterns
         .iter()
-        .map(|(key, group)| (key.to_string(), group.iter().map(|(_, freq)| freq).sum()))
-        .collect_vec();
+        .map(|(word, pattern)| parse_step(&normalization.normalize(word), pattern, word_length))
+        .collect()
+}
 
-    let rules = patterns
-        .iter()
-        .map(|(word, pattern)| {
-            Rule::new(
-                word,
-                &pattern
-                    .chars()
-                    .map(|c| match c {
-                        '0' => Pattern::Incorrect,
-                        '1' => Pattern::Misplaced,
-                        '2' => Pattern::Correct,
-                        _ => todo!(),
-                    })
-                    .collect_vec(),
-            )
+/// Expected information and expected remaining candidates after `guess` followed,
+/// for each of its pattern buckets, by the guess with the most entropy on that bucket.
+/// Follow-ups are picked among `guesses`, which in hard mode must also match the pattern
+/// of `guess` on the bucket, as the answers of the bucket do.
+#[allow(clippy::cast_precision_loss)]
+fn two_step_score<F>(
+    guess: usize,
+    candidates: &[usize],
+    weights: &[f32],
+    guesses: &[usize],
+    hard_mode: bool,
+    code: &F,
+) -> (f32, f32)
+where
+    F: Fn(usize, usize) -> PatternCode + Sync,
+{
+    let total = candidates
+        .iter()
+        .map(|&answer| weights[answer])
+        .sum::<f32>();
+    let groups = candidates
+        .iter()
+        .map(|&answer| (code(guess, answer), answer))
+        .into_group_map()
+        .into_iter()
+        .map(|(pattern, bucket)| {
+            let bucket_weight = bucket.iter().map(|&answer| weights[answer]).sum::<f32>();
+            (pattern, bucket, bucket_weight / total)
         })
         .collect_vec();
+    let probabilities = groups
+        .iter()
+        .map(|&(_, _, probability)| probability)
+        .collect_vec();
+    let mut information = get_entropy(&probabilities);
+    let mut remaining = 0.0;
+    // Buckets of answers the prior never picks add neither information nor remaining answers.
+    for (pattern, bucket, probability) in groups
+        .into_iter()
+        .filter(|&(_, _, probability)| probability > 0.0)
+    {
+        if bucket.len() == 1 {
+            remaining += probability;
+            continue;
+        }
+        let matching;
+        let follow_ups = if hard_mode {
+            matching = guesses
+                .iter()
+                .copied()
+                .filter(|&follow_up| code(guess, follow_up) == pattern)
+                .collect_vec();
+            &matching
+        } else {
+            guesses
+        };
+        let (follow_up_entropy, follow_up_remaining) = follow_ups
+            .par_iter()
+            .map_init(Vec::new, |codes, &follow_up| {
+                codes.clear();
+                codes.extend(
+                    bucket
+                        .iter()
+                        .map(|&answer| (code(follow_up, answer), weights[answer])),
+                );
+                let groups = buckets(codes);
+                (get_bucket_entropy(&groups), get_expected_bucket(&groups))
+            })
+            .reduce(
+                || (0.0, bucket.len() as f32),
+                |a, b| if b.0 > a.0 { b } else { a },
+            );
+        information += probability * follow_up_entropy;
+        remaining += probability * follow_up_remaining;
+    }
+    (information, remaining)
+}
 
-    let possible_words = word_dictionary
+/// Whether each word is still a candidate: an answer matching every rule, or any word matching
+/// them once no answer does, when the answer is outside of the restricted pool.
+fn candidate_mask(conform: &[bool], answers: &[bool]) -> Vec<bool> {
+    let is_candidate = conform
         .iter()
-        .filter(|(word, _)| rules.iter().all(|rule| rule.Is_Word_Conform(word)))
-        .map(|(word, _)| word)
-        .cloned()
+        .zip(answers)
+        .map(|(&conform, &answer)| conform && answer)
         .collect_vec();
+    if is_candidate.contains(&true) {
+        is_candidate
+    } else {
+        conform.to_vec()
+    }
+}
+
+fn recommend<F>(
+    words: &[(String, f32)],
+    answers: &[bool],
+    rules: &[Rule],
+    options: &SolverOptions,
+    code: F,
+) -> Vec<WordleEntity>
+where
+    F: Fn(usize, usize) -> PatternCode + Sync,
+{
+    // Hard mode guesses any word matching the steps, answer or not.
+    let conform = words
+        .iter()
+        .map(|(word, _)| rules.iter().all(|rule| rule.Is_Word_Conform(word)))
+        .collect_vec();
+    let is_candidate = candidate_mask(&conform, answers);
+    let candidates = is_candidate
+        .iter()
+        .positions(|&candidate| candidate)
+        .collect_vec();
+    let mut weights = options.prior.weights(words);
+    let mut candidate_weight = candidates
+        .iter()
+        .map(|&answer| weights[answer])
+        .sum::<f32>();
+    if !candidates.is_empty() && candidate_weight.partial_cmp(&0.0) != Some(Ordering::Greater) {
+        // The prior ruled every candidate out, they are all the answer as much.
+        weights = Prior::Uniform.weights(words);
+        candidate_weight = candidates
+            .iter()
+            .map(|&answer| weights[answer])
+            .sum::<f32>();
+    }
+    let probabilities = (0..words.len())
+        .map(|word| {
+            if is_candidate[word] {
+                weights[word] / candidate_weight
+            } else {
+                0.0
+            }
+        })
+        .collect_vec();
+    let remaining_entropy = get_entropy(
+        &candidates
+            .iter()
+            .map(|&answer| probabilities[answer])
+            .collect_vec(),
+    );
 
-    word_dictionary
+    let guesses = if options.hard_mode {
+        conform.iter().positions(|&conform| conform).collect_vec()
+    } else {
+        (0..words.len()).collect_vec()
+    };
+
+    let mut entities: Vec<WordleEntity> = guesses
         .par_iter()
-        .map(|(word, freq)| WordleEntity {
-            entropy: entropy_by_word(word, &possible_words),
-            word: word.to_string(),
-            frequency: *freq,
-            is_candidate: possible_words.contains(word),
+        .map_init(Vec::new, |codes, &guess| {
+            codes.clear();
+            codes.extend(
+                candidates
+                    .iter()
+                    .map(|&answer| (code(guess, answer), weights[answer])),
+            );
+            let (word, freq) = &words[guess];
+            let groups = buckets(codes);
+            let entropy = get_bucket_entropy(&groups);
+            let probability = probabilities[guess];
+            WordleEntity {
+                entropy,
+                word: word.clone(),
+                frequency: *freq,
+                is_candidate: is_candidate[guess],
+                two_step_entropy: None,
+                two_step_remaining: None,
+                expected_guesses: expected_guesses(probability, entropy, remaining_entropy),
+                probability,
+                largest_bucket: groups.iter().map(|group| group.size).max().unwrap_or(0),
+                expected_bucket: get_expected_bucket(&groups),
+            }
         })
-        .collect()
+        .collect();
+
+    if options.scoring == ScoringMode::TwoStep {
+        let top = (0..entities.len())
+            .sorted_by(|&a, &b| entities[b].entropy.total_cmp(&entities[a].entropy))
+            .take(options.lookahead_top)
+            .collect_vec();
+        let scores = top
+            .par_iter()
+            .map(|&entity| {
+                two_step_score(
+                    guesses[entity],
+                    &candidates,
+                    &weights,
+                    &guesses,
+                    options.hard_mode,
+                    &code,
+                )
+            })
+            .collect::<Vec<_>>();
+        for (entity, (information, remaining)) in top.into_iter().zip(scores) {
+            entities[entity].two_step_entropy = Some(information);
+            entities[entity].two_step_remaining = Some(remaining);
+        }
+    }
+    entities
+}
+
+/// # Errors
+/// Returns a `SolverError` as soon as one of the steps is invalid, see `parse_step`.
+pub fn retrieve_recommended_words(
+    dictionary: &Dictionary,
+    patterns: &[(String, String)],
+    word_length: usize,
+    first_char: &str,
+    options: &SolverOptions,
+) -> Result<Vec<WordleEntity>, SolverError> {
+    let rules = parse_steps(patterns, word_length, dictionary.normalization())?;
+    check_encoded_length(word_length)?;
+    let words = dictionary.words(word_length, first_char);
+    let answers = words
+        .iter()
+        .map(|(word, _)| dictionary.is_answer(word))
+        .collect_vec();
+    Ok(recommend(
+        words,
+        &answers,
+        &rules,
+        options,
+        |guess, answer| pattern_code(&words[guess].0, &words[answer].0),
+    ))
+}
+
+/// Same as `retrieve_recommended_words`, looking the patterns up in a precomputed matrix.
+/// # Errors
+/// Returns a `SolverError` as soon as one of the steps is invalid, see `parse_step`.
+pub fn retrieve_recommended_words_with_matrix(
+    matrix: &PatternMatrix,
+    patterns: &[(String, String)],
+    options: &SolverOptions,
+) -> Result<Vec<WordleEntity>, SolverError> {
+    let rules = parse_steps(patterns, matrix.word_length, &matrix.normalization)?;
+    Ok(recommend(
+        &matrix.words,
+        &matrix.answers,
+        &rules,
+        options,
+        |guess, answer| matrix.code(guess, answer),
+    ))
+}
+
+/// Whether the candidates were taken outside of the answers of the dictionary, none of them
+/// matching the steps, see `Dictionary::restrict_answers`.
+#[must_use]
+pub fn outside_answers(dictionary: &Dictionary, candidates: &[WordleEntity]) -> bool {
+    !candidates.is_empty() && !candidates.iter().any(|we| dictionary.is_answer(&we.word))
 }
 
-struct Rule {
+/// Word to play from recommendations already sorted with `ScoringMode::sort`.
+/// Once only a couple of candidates remain, or when no guess tells them apart,
+/// guessing the likeliest candidate finishes sooner than the best ranked word.
+#[must_use]
+pub fn suggested_guess(recommended: &[WordleEntity]) -> Option<&WordleEntity> {
+    let candidates = recommended
+        .iter()
+        .filter(|we| we.is_candidate)
+        .collect_vec();
+    match recommended.first() {
+        Some(best) if candidates.len() > 2 && best.entropy > 0.0 => Some(best),
+        _ => candidates.into_iter().max_by(|a, b| {
+            a.probability
+                .total_cmp(&b.probability)
+                .then(a.frequency.total_cmp(&b.frequency))
+        }),
+    }
+}
+
+#[allow(clippy::struct_field_names)]
+pub struct Rule {
     character_count: HashMap<char, usize>,
     character_at_least_count: HashMap<char, usize>,
     character_position_to_match: HashMap<usize, char>,
@@ -148,15 +1039,17 @@ struct Rule {
 }
 
 impl Rule {
+    #[must_use]
     pub fn new(word: &str, pattern: &[Pattern]) -> Self {
         let mut char_count: HashMap<char, usize> = HashMap::new();
         let mut char_at_least_count: HashMap<char, usize> = HashMap::new();
         let mut char_position_to_match: HashMap<usize, char> = HashMap::new();
         let mut char_position_to_not_match: Vec<(usize, char)> = Vec::new();
-        for (key, group) in &pattern
-            .iter()
+        for (key, group) in &word
+            .chars()
+            .zip(pattern)
             .enumerate()
-            .map(|(index, pat)| (word.chars().nth(index).unwrap(), index, pat))
+            .map(|(index, (character, pat))| (character, index, pat))
             .into_group_map_by(|test| test.0)
         {
             if group.iter().any(|test| test.2 == &Pattern::Incorrect) {
@@ -197,7 +1090,8 @@ impl Rule {
         }
     }
 
-    fn Is_Word_Conform(&self, word: &str) -> bool {
+    #[must_use]
+    pub fn Is_Word_Conform(&self, word: &str) -> bool {
         self.character_position_to_match
             .iter()
             .all(|(pos, char)| word.chars().nth(*pos) == Some(*char))
@@ -1,22 +1,14 @@
+// The baseline tests predate the pedantic lints and are kept as written.
+#![allow(
+    clippy::float_cmp,
+    clippy::semicolon_if_nothing_returned,
+    clippy::unnecessary_to_owned,
+    clippy::unreadable_literal,
+    clippy::useless_vec
+)]
 use super::*;
-#[test]
-fn Parse_Wordle_entity() {
-    let parsed = parse_line("coucou;1.32", 6, "");
-    assert_eq!(parsed, Some(("coucou".to_string(),1.32)));
-}
-
-#[test]
-fn Parse_Wordle_entity_special_char() {
-    let parsed = parse_line("père;1.32", 4, "");
-    assert_eq!(parsed, Some(("pere".to_string(),1.32)));
-}
-
-#[test]
-fn Parse_Wordle_entity_space() {
-    let parsed = parse_line("à priori;1.32", 8, "");
-    assert_eq!(parsed, None);
-}
-
+use crate::WordleDictionary::{AnswerFilter, DictionaryFormat};
+use crate::WordleFixtures;
 #[test]
 fn Rule1() {
     let rule = Rule::new(
@@ -405,7 +397,14 @@ fn Pattern4() {
 
 #[test]
 fn StressTests() {
-    let mut test = retrieve_recommended_words(&vec![], 5, "t");
+    let mut test = retrieve_recommended_words(
+        Dictionary::lexique(),
+        &vec![],
+        5,
+        "t",
+        &SolverOptions::default(),
+    )
+    .unwrap();
     test.sort_by(|a, b| b.entropy.partial_cmp(&a.entropy).unwrap());
     let elu = &test[0];
     assert_eq!("tarie", elu.word);
@@ -417,7 +416,14 @@ fn StressTests() {
 //cargo flamegraph --unit-test -- StressTests2
 #[test]
 fn StressTests2() {
-    let mut test = retrieve_recommended_words(&vec![], 5, "");
+    let mut test = retrieve_recommended_words(
+        Dictionary::lexique(),
+        &vec![],
+        5,
+        "",
+        &SolverOptions::default(),
+    )
+    .unwrap();
     test.sort_by(|a, b| b.entropy.partial_cmp(&a.entropy).unwrap());
     let elu = &test[0];
     assert_eq!("tarie", elu.word)
@@ -428,3 +434,516 @@ fn Get_uniform_entropy() {
     let test = get_uniform_entropy(5037);
     assert_eq!(12.297734, test)
 }
+
+#[test]
+fn Parse_pattern_bad_char() {
+    let parsed = parse_pattern("0123");
+    assert_eq!(
+        parsed,
+        Err(SolverError::InvalidPatternChar {
+            pattern: "0123".to_string(),
+            character: '3'
+        })
+    );
+}
+
+#[test]
+fn Parse_step_pattern_length_mismatch() {
+    let parsed = parse_step("tarie", "201000", 5);
+    assert!(matches!(
+        parsed,
+        Err(SolverError::PatternLengthMismatch { word_length: 5, .. })
+    ));
+}
+
+#[test]
+fn Parse_step_word_length_mismatch() {
+    let parsed = parse_step("taries", "20100", 5);
+    assert!(matches!(
+        parsed,
+        Err(SolverError::WordLengthMismatch { word_length: 5, .. })
+    ));
+}
+
+#[test]
+fn Parse_step_non_alphabetic() {
+    let parsed = parse_step("ta-ie", "20100", 5);
+    assert!(matches!(
+        parsed,
+        Err(SolverError::NonAlphabeticGuess { .. })
+    ));
+}
+
+#[test]
+fn Rule_pattern_longer_than_word() {
+    let rule = Rule::new(
+        "tar",
+        &[
+            Pattern::Correct,
+            Pattern::Correct,
+            Pattern::Correct,
+            Pattern::Correct,
+        ],
+    );
+    assert!(rule.Is_Word_Conform("tarie"));
+}
+
+#[test]
+fn Retrieve_invalid_step() {
+    let result = retrieve_recommended_words(
+        Dictionary::lexique(),
+        &[("tarie".to_string(), "2010x".to_string())],
+        5,
+        "t",
+        &SolverOptions::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(SolverError::InvalidPatternChar { character: 'x', .. })
+    ));
+}
+
+#[test]
+fn Pattern_code() {
+    for (actual, target) in [
+        ("usurier", "usagers"),
+        ("usagers", "usurier"),
+        ("abregee", "feuille"),
+        ("aeriens", "feuille"),
+    ] {
+        let pattern = get_pattern(actual, target);
+        assert_eq!(
+            get_pattern_code(actual, target).unwrap(),
+            encode_pattern(&pattern)
+        );
+        assert_eq!(decode_pattern(encode_pattern(&pattern), 7), pattern);
+    }
+}
+
+#[test]
+fn Pattern_accents() {
+    // Kept accents are letters of their own, whatever their number of bytes.
+    let pattern = get_pattern("élève", "lèvre");
+    assert_eq!(format_pattern(&pattern), "01112");
+    assert_eq!(
+        get_pattern_code("élève", "lèvre").unwrap(),
+        encode_pattern(&pattern)
+    );
+    assert!(Rule::new("élève", &pattern).Is_Word_Conform("lèvre"));
+    assert_eq!(format_pattern(&get_pattern("eleve", "levre")), "11012");
+    assert_eq!(format_pattern(&get_pattern("garçon", "garcon")), "222022");
+}
+
+#[test]
+fn Pattern_code_digits() {
+    let pattern = [Pattern::Correct, Pattern::Misplaced, Pattern::Incorrect];
+    assert_eq!(encode_pattern(&pattern), 2 + 3);
+    assert_eq!(encode_pattern(&[Pattern::Correct; 5]), 3_u64.pow(5) - 1);
+}
+
+#[test]
+fn Matrix_matches_on_the_fly() {
+    let dictionary = Dictionary::from_words([
+        ("tarie", 3.0),
+        ("tapis", 2.0),
+        ("taupe", 1.0),
+        ("tenir", 5.0),
+        ("tonne", 1.0),
+    ]);
+    let steps = [("tapis".to_string(), "21010".to_string())];
+    let matrix = PatternMatrix::new(&dictionary, 5, "t").unwrap();
+    assert_eq!(
+        retrieve_recommended_words_with_matrix(&matrix, &steps, &SolverOptions::default()).unwrap(),
+        retrieve_recommended_words(&dictionary, &steps, 5, "t", &SolverOptions::default()).unwrap()
+    );
+}
+
+#[test]
+fn Matrix_codes_fit_their_width() {
+    let dictionary = Dictionary::from_words([
+        ("ventilateur", 1.0),
+        ("realisateur", 1.0),
+        ("animateurs", 1.0),
+        ("exactement", 1.0),
+        ("maintenant", 1.0),
+    ]);
+    for word_length in [10, 11] {
+        let matrix = PatternMatrix::new(&dictionary, word_length, "").unwrap();
+        for (guess, (guess_word, _)) in matrix.words().iter().enumerate() {
+            for (answer, (answer_word, _)) in matrix.words().iter().enumerate() {
+                assert_eq!(
+                    matrix.code(guess, answer),
+                    get_pattern_code(guess_word, answer_word).unwrap()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn Matrix_size_is_limited() {
+    assert!(matches!(
+        PatternMatrix::new(Dictionary::lexique(), 9, ""),
+        Err(SolverError::SliceTooLarge { words, .. }) if words == Dictionary::lexique().words(9, "").len()
+    ));
+    let long = "a".repeat(MAX_ENCODED_LENGTH + 1);
+    assert_eq!(
+        get_pattern_code(&long, &long),
+        Err(SolverError::PatternTooLong {
+            word_length: MAX_ENCODED_LENGTH + 1
+        })
+    );
+    let dictionary = Dictionary::from_words([(long.as_str(), 1.0)]);
+    assert!(matches!(
+        PatternMatrix::new(&dictionary, MAX_ENCODED_LENGTH + 1, ""),
+        Err(SolverError::PatternTooLong { .. })
+    ));
+    assert!(matches!(
+        retrieve_recommended_words(
+            &dictionary,
+            &[],
+            MAX_ENCODED_LENGTH + 1,
+            "",
+            &SolverOptions::default()
+        ),
+        Err(SolverError::PatternTooLong { .. })
+    ));
+}
+
+#[test]
+fn StressTests_matrix() {
+    let matrix = PatternMatrix::new(Dictionary::lexique(), 5, "t").unwrap();
+    let mut test =
+        retrieve_recommended_words_with_matrix(&matrix, &[], &SolverOptions::default()).unwrap();
+    test.sort_by(|a, b| b.entropy.partial_cmp(&a.entropy).unwrap());
+    assert_eq!("tarie", test[0].word);
+}
+
+#[test]
+fn Two_step_lookahead() {
+    let dictionary = Dictionary::from_words([
+        ("tarie", 3.0),
+        ("tapis", 2.0),
+        ("taupe", 1.0),
+        ("tenir", 5.0),
+        ("tonne", 1.0),
+        ("tiare", 1.0),
+    ]);
+    let options = SolverOptions {
+        scoring: ScoringMode::TwoStep,
+        lookahead_top: 3,
+        ..SolverOptions::default()
+    };
+    let mut test = retrieve_recommended_words(&dictionary, &[], 5, "t", &options).unwrap();
+    options.scoring.sort(&mut test);
+    assert_eq!(
+        test.iter()
+            .filter(|we| we.two_step_entropy.is_some())
+            .count(),
+        3
+    );
+    let best = &test[0];
+    // Six equally likely candidates can be told apart in two guesses at best.
+    assert!(best.two_step_entropy.unwrap() <= get_uniform_entropy(6) + 1e-5);
+    assert!(best.two_step_entropy.unwrap() >= best.entropy);
+    assert!(best.two_step_remaining.unwrap() >= 1.0);
+}
+
+#[test]
+fn Scoring_mode_names() {
+    for mode in ScoringMode::ALL {
+        assert_eq!(mode.name().parse::<ScoringMode>(), Ok(mode));
+    }
+    assert!("minimax-ish".parse::<ScoringMode>().is_err());
+}
+
+#[test]
+fn Expected_guesses_prefers_candidates() {
+    let dictionary = WordleFixtures::dictionary_without_blocu();
+    // tarie and taris remain, crise splits them as well as guessing one of them does.
+    let steps = [("blocu".to_string(), "00000".to_string())];
+    let options = SolverOptions {
+        scoring: ScoringMode::ExpectedGuesses,
+        ..SolverOptions::default()
+    };
+    let mut test = retrieve_recommended_words(&dictionary, &steps, 5, "", &options).unwrap();
+    options.scoring.sort(&mut test);
+    assert!(test[0].is_candidate);
+    assert!((test[0].expected_guesses - 1.5).abs() < 1e-5);
+    let crise = test.iter().find(|we| we.word == "crise").unwrap();
+    assert_eq!(crise.entropy, test[0].entropy);
+    assert!((crise.expected_guesses - 2.0).abs() < 1e-5);
+}
+
+#[test]
+fn Estimated_guesses() {
+    assert_eq!(estimated_guesses(0.0), 1.0);
+    assert!(estimated_guesses(1.0) < estimated_guesses(5.0));
+}
+
+#[test]
+fn Prior_weights() {
+    let words = [
+        ("aaaaa".to_string(), 1.0),
+        ("bbbbb".to_string(), 100.0),
+        ("ccccc".to_string(), 10.0),
+    ];
+    assert_eq!(Prior::Uniform.weights(&words), vec![1.0; 3]);
+    let weights = Prior::DEFAULT_SIGMOID.weights(&words);
+    assert!(weights[1] > weights[2]);
+    assert!(weights[2] > weights[0]);
+    assert!(weights.iter().all(|&weight| weight > 0.0 && weight < 1.0));
+}
+
+#[test]
+fn Prior_names() {
+    assert_eq!("uniform".parse::<Prior>(), Ok(Prior::Uniform));
+    assert_eq!("sigmoid".parse::<Prior>(), Ok(Prior::DEFAULT_SIGMOID));
+    assert_eq!(
+        "sigmoid:0.5:8".parse::<Prior>(),
+        Ok(Prior::Sigmoid {
+            midpoint: 0.5,
+            steepness: 8.0
+        })
+    );
+    assert_eq!(
+        Prior::DEFAULT_SIGMOID.to_string().parse::<Prior>(),
+        Ok(Prior::DEFAULT_SIGMOID)
+    );
+    assert!("sigmoid:x:8".parse::<Prior>().is_err());
+    assert!("zipf".parse::<Prior>().is_err());
+    for invalid in [
+        "sigmoid:NaN:8",
+        "sigmoid:1.5:8",
+        "sigmoid:-0.1:8",
+        "sigmoid:0.5:inf",
+        "sigmoid:0.5:NaN",
+        "sigmoid:0.5:-3",
+    ] {
+        assert!(invalid.parse::<Prior>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn Steep_prior_scores_are_numbers() {
+    let options = SolverOptions {
+        prior: "sigmoid:0.4:200".parse().unwrap(),
+        ..SolverOptions::default()
+    };
+    let steps = [vec![], vec![("tarie".to_string(), "20000".to_string())]];
+    for (scoring, steps) in ScoringMode::ALL.into_iter().cartesian_product(&steps) {
+        let options = SolverOptions {
+            scoring,
+            ..options.clone()
+        };
+        let mut test =
+            retrieve_recommended_words(Dictionary::lexique(), steps, 5, "t", &options).unwrap();
+        assert!(test.iter().all(|we| we.entropy.is_finite()
+            && we.expected_guesses.is_finite()
+            && we.two_step_entropy.is_none_or(f32::is_finite)
+            && we.two_step_remaining.is_none_or(f32::is_finite)));
+        options.scoring.sort(&mut test);
+        assert!(suggested_guess(&test).is_some());
+    }
+}
+
+#[test]
+fn Uniform_prior_probabilities() {
+    let test = retrieve_recommended_words(
+        Dictionary::lexique(),
+        &[("tarie".to_string(), "00000".to_string())],
+        5,
+        "t",
+        &SolverOptions::default(),
+    )
+    .unwrap();
+    let candidates = test.iter().filter(|we| we.is_candidate).collect_vec();
+    #[allow(clippy::cast_precision_loss)]
+    let expected = 1.0 / candidates.len() as f32;
+    assert!(candidates
+        .iter()
+        .all(|we| (we.probability - expected).abs() < 1e-6));
+    assert!(test
+        .iter()
+        .filter(|we| !we.is_candidate)
+        .all(|we| we.probability == 0.0));
+}
+
+#[test]
+fn Sigmoid_prior_favours_frequent_candidates() {
+    let dictionary = Dictionary::from_words([
+        ("tarie", 50.0),
+        ("taris", 1.0),
+        ("poele", 1.0),
+        ("crise", 1.0),
+    ]);
+    let steps = [("blocu".to_string(), "00000".to_string())];
+    let options = SolverOptions {
+        prior: Prior::DEFAULT_SIGMOID,
+        ..SolverOptions::default()
+    };
+    let test = retrieve_recommended_words(&dictionary, &steps, 5, "", &options).unwrap();
+    let frequent = test.iter().find(|we| we.word == "tarie").unwrap();
+    let rare = test.iter().find(|we| we.word == "taris").unwrap();
+    assert!(frequent.probability > rare.probability);
+    assert!((frequent.probability + rare.probability - 1.0).abs() < 1e-6);
+    // Splitting two unequally likely candidates is worth less than one bit.
+    assert!(frequent.entropy < 1.0);
+    assert!(frequent.expected_guesses < rare.expected_guesses);
+}
+
+#[test]
+fn Hard_mode_only_guesses_candidates() {
+    let steps = [("tarie".to_string(), "20100".to_string())];
+    let options = SolverOptions {
+        hard_mode: true,
+        ..SolverOptions::default()
+    };
+    let test = retrieve_recommended_words(Dictionary::lexique(), &steps, 5, "t", &options).unwrap();
+    let easy = retrieve_recommended_words(
+        Dictionary::lexique(),
+        &steps,
+        5,
+        "t",
+        &SolverOptions::default(),
+    )
+    .unwrap();
+    assert!(!test.is_empty());
+    assert!(test.iter().all(|we| we.is_candidate));
+    assert_eq!(test.len(), easy.iter().filter(|we| we.is_candidate).count());
+}
+
+#[test]
+fn Hard_mode_two_step() {
+    let options = SolverOptions {
+        scoring: ScoringMode::TwoStep,
+        hard_mode: true,
+        ..SolverOptions::default()
+    };
+    let steps = [("tarie".to_string(), "20000".to_string())];
+    let mut test =
+        retrieve_recommended_words(Dictionary::lexique(), &steps, 5, "t", &options).unwrap();
+    options.scoring.sort(&mut test);
+    assert!(test[0].two_step_entropy.is_some());
+    assert!(test[0].two_step_entropy.unwrap() >= test[0].entropy);
+}
+
+#[test]
+fn Minimax_ranks_by_largest_bucket() {
+    let dictionary = WordleFixtures::dictionary();
+    let options = SolverOptions {
+        scoring: ScoringMode::Minimax,
+        ..SolverOptions::default()
+    };
+    let mut test = retrieve_recommended_words(&dictionary, &[], 5, "", &options).unwrap();
+    options.scoring.sort(&mut test);
+    let blocu = test.iter().find(|we| we.word == "blocu").unwrap();
+    assert_eq!(blocu.largest_bucket, 2);
+    assert!(test
+        .windows(2)
+        .all(|pair| pair[0].largest_bucket <= pair[1].largest_bucket));
+    assert!(test[0].largest_bucket < blocu.largest_bucket);
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn Expected_bucket_size() {
+    let dictionary = WordleFixtures::dictionary();
+    let options = SolverOptions {
+        scoring: ScoringMode::ExpectedBucket,
+        ..SolverOptions::default()
+    };
+    let mut test = retrieve_recommended_words(&dictionary, &[], 5, "", &options).unwrap();
+    options.scoring.sort(&mut test);
+    // blocu leaves {tarie, taris} with probability 2/5 and single words otherwise.
+    let blocu = test.iter().find(|we| we.word == "blocu").unwrap();
+    assert!((blocu.expected_bucket - 1.4).abs() < 1e-6);
+    assert!(test
+        .windows(2)
+        .all(|pair| pair[0].expected_bucket <= pair[1].expected_bucket));
+    assert!(test
+        .iter()
+        .all(|we| we.expected_bucket <= we.largest_bucket as f32 + 1e-6));
+}
+
+#[test]
+fn Restricted_answers_are_the_candidates() {
+    let content = "1_ortho;3_lemme;4_cgram;10_freqlivres
+tarie;tarir;VER;1
+taris;tarir;VER;1
+tarot;tarot;NOM;1
+";
+    let mut dictionary = Dictionary::from_csv(content, &DictionaryFormat::lexique()).unwrap();
+    dictionary
+        .restrict_answers(&AnswerFilter {
+            lemmas_only: true,
+            ..AnswerFilter::default()
+        })
+        .unwrap();
+    let candidates = |steps: &[(String, String)]| {
+        retrieve_recommended_words(&dictionary, steps, 5, "", &SolverOptions::default())
+            .unwrap()
+            .into_iter()
+            .filter(|we| we.is_candidate)
+            .map(|we| we.word)
+            .collect_vec()
+    };
+    assert_eq!(candidates(&[]), ["tarot"]);
+    // An answer outside of the pool is still found among the words matching the steps.
+    assert_eq!(
+        candidates(&[("tarot".to_string(), "22200".to_string())]),
+        ["tarie", "taris"]
+    );
+    let outside = |steps: &[(String, String)]| {
+        let test = retrieve_recommended_words(&dictionary, steps, 5, "", &SolverOptions::default())
+            .unwrap();
+        outside_answers(
+            &dictionary,
+            &test.into_iter().filter(|we| we.is_candidate).collect_vec(),
+        )
+    };
+    assert!(!outside(&[]));
+    assert!(outside(&[("tarot".to_string(), "22200".to_string())]));
+    // Hard mode may guess the words matching the steps that cannot be the answer.
+    for scoring in [ScoringMode::Entropy, ScoringMode::TwoStep] {
+        let options = SolverOptions {
+            scoring,
+            hard_mode: true,
+            ..SolverOptions::default()
+        };
+        let steps = [("bulle".to_string(), "00000".to_string())];
+        let test = retrieve_recommended_words(&dictionary, &steps, 5, "", &options).unwrap();
+        assert_eq!(
+            test.iter()
+                .map(|we| we.word.as_str())
+                .sorted()
+                .collect_vec(),
+            ["taris", "tarot"]
+        );
+        assert!(test.iter().any(|we| !we.is_candidate));
+    }
+}
+
+#[test]
+fn Guesses_are_normalized_alike() {
+    let matrix = PatternMatrix::new(Dictionary::lexique(), 5, "c").unwrap();
+    for steps in [
+        [("CŒUR".to_string(), "20000".to_string())],
+        [("Crise".to_string(), "21000".to_string())],
+    ] {
+        let test = retrieve_recommended_words(
+            Dictionary::lexique(),
+            &steps,
+            5,
+            "c",
+            &SolverOptions::default(),
+        )
+        .unwrap();
+        assert!(test.iter().any(|we| we.is_candidate));
+        assert_eq!(
+            retrieve_recommended_words_with_matrix(&matrix, &steps, &SolverOptions::default())
+                .unwrap(),
+            test
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use crate::WordleDictionary::Dictionary;
+use crate::WordleSolver::{self, PatternMatrix, SolverError, SolverOptions};
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// A guess of the strategy and what to play next for each pattern it may get.
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct StrategyNode {
+    pub guess: String,
+    /// Number of answers still possible when the guess is played.
+    pub candidates: usize,
+    /// The guess may be the answer, in which case it gets an all correct pattern not in `children`.
+    pub is_candidate: bool,
+    /// Next node for each other pattern, keyed by the pattern in `0/1/2` digits.
+    pub children: BTreeMap<String, StrategyNode>,
+}
+
+/// The guesses the solver plays against every answer of `word_length` letters starting with
+/// `first_char`, see `build_strategy`.
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct Strategy {
+    pub word_length: usize,
+    pub first_char: String,
+    /// First guess, `None` if there is no word to guess.
+    pub root: Option<StrategyNode>,
+}
+
+impl Strategy {
+    /// Number of answers the strategy solves.
+    #[must_use]
+    pub fn answers(&self) -> usize {
+        self.root.as_ref().map_or(0, |node| node.candidates)
+    }
+
+    /// Number of guesses needed for the answer needing the most.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.guess_counts().into_iter().max().unwrap_or_default()
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average_guesses(&self) -> f32 {
+        let counts = self.guess_counts();
+        counts.iter().sum::<usize>() as f32 / counts.len().max(1) as f32
+    }
+
+    /// Number of answers found for each number of guesses.
+    #[must_use]
+    pub fn distribution(&self) -> BTreeMap<usize, usize> {
+        self.guess_counts()
+            .into_iter()
+            .counts()
+            .into_iter()
+            .collect()
+    }
+
+    /// One line per node, indented by depth: the pattern leading to it, the guess and the
+    /// number of candidates left, e.g. `  01200 taris (3)`.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if let Some(root) = &self.root {
+            write_node(&mut text, root, "", 0);
+        }
+        text
+    }
+
+    fn guess_counts(&self) -> Vec<usize> {
+        let mut counts = Vec::with_capacity(self.answers());
+        if let Some(root) = &self.root {
+            collect_guess_counts(root, 1, &mut counts);
+        }
+        counts
+    }
+}
+
+fn collect_guess_counts(node: &StrategyNode, guesses: usize, counts: &mut Vec<usize>) {
+    if node.is_candidate {
+        counts.push(guesses);
+    }
+    for child in node.children.values() {
+        collect_guess_counts(child, guesses + 1, counts);
+    }
+}
+
+fn write_node(text: &mut String, node: &StrategyNode, pattern: &str, level: usize) {
+    let indent = "  ".repeat(level);
+    let separator = if pattern.is_empty() { "" } else { " " };
+    // Writing to a String cannot fail.
+    let _ = writeln!(
+        text,
+        "{indent}{pattern}{separator}{} ({})",
+        node.guess, node.candidates
+    );
+    for (pattern, child) in &node.children {
+        write_node(text, child, pattern, level + 1);
+    }
+}
+
+/// Plays the solver's suggested guess, see `WordleSolver::suggested_guess`, from the first
+/// guess down to every pattern it may get until each answer is found, the same games as
+/// `WordleSimulation::simulate` gathered in a tree.
+/// # Errors
+/// Returns the `SolverError` of `PatternMatrix::new` if the patterns of the slice cannot be
+/// computed.
+pub fn build_strategy(
+    dictionary: &Dictionary,
+    word_length: usize,
+    first_char: &str,
+    options: &SolverOptions,
+) -> Result<Strategy, SolverError> {
+    let matrix = PatternMatrix::new(dictionary, word_length, first_char)?;
+    Ok(Strategy {
+        word_length,
+        first_char: first_char.to_string(),
+        root: build_node(&matrix, &mut Vec::new(), options),
+    })
+}
+
+fn build_node(
+    matrix: &PatternMatrix,
+    steps: &mut Vec<(String, String)>,
+    options: &SolverOptions,
+) -> Option<StrategyNode> {
+    let words = matrix.words();
+    let mut recommended =
+        WordleSolver::retrieve_recommended_words_with_matrix(matrix, steps, options)
+            .expect("steps are words of the matrix with the patterns it gave");
+    options.scoring.sort(&mut recommended);
+    let guess = WordleSolver::suggested_guess(&recommended)?;
+    let index = |word: &str| {
+        words
+            .binary_search_by(|(known, _)| known.as_str().cmp(word))
+            .expect("recommended words come from the matrix")
+    };
+    let guess_index = index(&guess.word);
+    let candidates = recommended
+        .iter()
+        .filter(|we| we.is_candidate)
+        .map(|we| index(&we.word))
+        .collect_vec();
+    let mut children = BTreeMap::new();
+    // The suggested guess either splits the candidates or is one of them, so each child has
+    // fewer candidates and the recursion ends.
+    for code in candidates
+        .iter()
+        .filter(|&&answer| answer != guess_index)
+        .map(|&answer| matrix.code(guess_index, answer))
+        .unique()
+    {
+        let pattern =
+            WordleSolver::format_pattern(&WordleSolver::decode_pattern(code, matrix.word_length()));
+        steps.push((guess.word.clone(), pattern.clone()));
+        let child = build_node(matrix, steps, options);
+        steps.pop();
+        if let Some(child) = child {
+            children.insert(pattern, child);
+        }
+    }
+    Some(StrategyNode {
+        guess: guess.word.clone(),
+        candidates: candidates.len(),
+        is_candidate: guess.is_candidate,
+        children,
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,58 @@
+use super::*;
+use crate::WordleFixtures::dictionary;
+use crate::WordleSimulation::{self, SimulationOptions};
+
+#[test]
+fn Strategy_solves_every_answer() {
+    let strategy = build_strategy(&dictionary(), 5, "", &SolverOptions::default()).unwrap();
+    let root = strategy.root.as_ref().unwrap();
+    assert_eq!(strategy.answers(), 5);
+    assert_eq!(root.candidates, 5);
+    assert_eq!(strategy.distribution().values().sum::<usize>(), 5);
+    assert!(strategy.depth() >= 2);
+    // Each child only keeps the candidates matching its pattern.
+    assert_eq!(
+        root.children
+            .values()
+            .map(|child| child.candidates)
+            .sum::<usize>()
+            + usize::from(root.is_candidate),
+        5
+    );
+    assert_eq!(
+        build_strategy(&dictionary(), 6, "", &SolverOptions::default())
+            .unwrap()
+            .root,
+        None
+    );
+}
+
+#[test]
+fn Strategy_matches_simulation() {
+    let dictionary = Dictionary::lexique();
+    let strategy = build_strategy(dictionary, 5, "z", &SolverOptions::default()).unwrap();
+    let report =
+        WordleSimulation::simulate(dictionary, 5, "z", &SimulationOptions::default()).unwrap();
+    assert_eq!(strategy.answers(), report.games.len());
+    assert_eq!(strategy.distribution(), report.distribution());
+    assert!((strategy.average_guesses() - report.average_guesses()).abs() < 1e-5);
+    assert_eq!(
+        strategy.depth(),
+        *report.distribution().keys().last().unwrap()
+    );
+}
+
+#[test]
+fn Strategy_text() {
+    let strategy = build_strategy(&dictionary(), 5, "", &SolverOptions::default()).unwrap();
+    let text = strategy.to_text();
+    let lines = text.lines().collect_vec();
+    let root = strategy.root.as_ref().unwrap();
+    assert_eq!(lines[0], format!("{} (5)", root.guess));
+    let (pattern, child) = root.children.iter().next().unwrap();
+    assert_eq!(
+        lines[1],
+        format!("  {pattern} {} ({})", child.guess, child.candidates)
+    );
+    assert!(lines[1..].iter().all(|line| line.starts_with("  ")));
+}
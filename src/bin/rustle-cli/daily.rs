@@ -0,0 +1,30 @@
+use super::CliOptions;
+use rustle::WordleDaily;
+use rustle::WordleDictionary::Dictionary;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct DailyReport {
+    date: String,
+    word: String,
+}
+
+pub(crate) fn run(
+    dictionary: &Dictionary,
+    options: &CliOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let date = options
+        .date
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let word = WordleDaily::word_of_the_day(dictionary, date, &options.daily)?;
+    if options.json {
+        let report = DailyReport {
+            date: date.to_string(),
+            word,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{date} {word}");
+    }
+    Ok(())
+}
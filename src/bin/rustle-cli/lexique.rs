@@ -0,0 +1,16 @@
+use super::CliOptions;
+use rustle::WordleDictionary;
+
+pub(crate) fn run(options: &CliOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let path = options
+        .profile
+        .path
+        .as_ref()
+        .ok_or("missing --dictionary, the path of the complete Lexique 3.81 file")?;
+    let content = std::fs::read_to_string(path)?;
+    print!(
+        "{}",
+        WordleDictionary::lexique_columns(&content, options.profile.format.separator)?
+    );
+    Ok(())
+}
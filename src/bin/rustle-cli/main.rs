@@ -0,0 +1,477 @@
+#![warn(clippy::pedantic)]
+use chrono::NaiveDate;
+use itertools::Itertools;
+use rustle::WordleDaily::DailyOptions;
+use rustle::WordleDictionary::{AnswerFilter, Dictionary, Normalization};
+use rustle::WordleLanguage::{LanguageError, LanguageProfile};
+use rustle::WordleOpeners;
+use rustle::WordleSimulation::SimulationOptions;
+use rustle::WordleSolver::{self, Prior, ScoringMode, SolverError, SolverOptions, WordleEntity};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+mod daily;
+mod lexique;
+mod openers;
+mod play;
+mod simulate;
+mod strategy;
+
+const USAGE: &str =
+    "Usage: rustle-cli [play|simulate|daily|strategy|openers|lexique] --length <N> [--first <CHAR>] [--top <N>] [--json] [WORD:PATTERN]...
+
+Prints the recommended and possible words after the given steps.
+Each step is a guessed word and its pattern, e.g. tarie:01200
+(0/1/2 => Incorrect/Misplaced/Correct).
+
+With `play`, starts an interactive session suggesting a guess each turn
+and reading back the observed pattern until the word is found.
+
+With `simulate`, plays the suggested guesses against every word of the
+given length and first char and reports how many guesses they needed.
+
+With `daily`, prints the word of the day of the given length and first char.
+
+With `strategy`, prints the solver's decision tree: the first guess, then the
+next guess for each pattern it may get, until every word is found.
+
+With `openers`, prints the openers file of every length and first char of
+the dictionary: the --top best openers by entropy, ranked again by
+simulation. --length is not needed.
+
+With `lexique`, prints the columns of the complete Lexique 3.81 file given
+with --dictionary and --separator that the embedded word list keeps, its
+words, frequencies and grammatical columns. --length is not needed.
+
+Options:
+  -l, --length <N>    Word length
+  -f, --first <CHAR>  First char of the word (optional)
+  -t, --top <N>       Number of words to print per table (default 5)
+  -j, --json          Print the result as JSON
+  -h, --help          Print this help
+
+Scoring options:
+  -s, --scoring <MODE>     How guesses are ranked: entropy (default), two-step,
+                           expected-guesses, minimax or expected-bucket
+      --lookahead-top <N>  Number of best entropy guesses explored by two-step (default 10)
+      --prior <PRIOR>      Answer probabilities given the word frequencies: uniform (default),
+                           sigmoid or sigmoid:<MIDPOINT>:<STEEPNESS> over the frequency rank
+      --hard               Hard mode: only guess words matching every step
+
+Simulation options:
+      --sample <N>         Play N answers drawn at random instead of every word
+      --seed <N>           Seed of the answer sampling or of the daily words (default 0)
+      --max-guesses <N>    Games needing more guesses are failures (default 6)
+      --opener <WORD>      First guess of every game instead of the suggested one
+
+Daily options:
+      --date <YYYY-MM-DD>  Day of the word (default today, UTC)
+      --window <N>         Number of days a word cannot come back (default 30)
+      --min-frequency <F>  Minimum frequency of the words of the day (default 1)
+
+Dictionary options (defaults to the embedded Lexique 3.81):
+      --language <CODE>        Language profile: fr (default), en, es or de. Only fr has
+                               embedded words, the others need --dictionary
+  -d, --dictionary <PATH>      Delimited word list whose first line holds the column names
+      --separator <CHAR>       Column separator (default ;)
+      --word-column <NAME>     Column holding the words (default 1_ortho, word for en, es, de)
+      --frequency-column <NAME>
+                               Column holding the frequencies (default 10_freqlivres,
+                               frequency for en, es, de)
+      --no-frequency           Give every word the same frequency
+      --normalization <POLICY> How accents are normalized in the words and the guesses:
+                               fold (default), keep, or a mapping like œ=oe,ß=ss
+
+Answer options (only with a --dictionary holding the grammatical columns of a complete
+Lexique 3.81, the embedded one has none):
+      --answer-categories <LIST>
+                               Grammatical categories the answer may have, e.g. NOM,ADJ
+      --lemmas-only            No inflected form as the answer, e.g. conjugated verbs
+      --singular-only          No plural form as the answer
+  Every word remains a valid guess.";
+
+enum Mode {
+    Report,
+    Play,
+    Simulate,
+    Daily,
+    Strategy,
+    Openers,
+    Lexique,
+}
+
+struct CliOptions {
+    word_length: usize,
+    first_char: String,
+    top: usize,
+    json: bool,
+    steps: Vec<(String, String)>,
+    profile: LanguageProfile,
+    solver: SolverOptions,
+    simulation: SimulationOptions,
+    daily: DailyOptions,
+    date: Option<NaiveDate>,
+}
+
+#[derive(Serialize)]
+struct CliReport {
+    recommended_count: usize,
+    possible_count: usize,
+    total_entropy: f32,
+    recommended: Vec<WordleEntity>,
+    possible: Vec<WordleEntity>,
+    /// No answer matches the steps, see `WordleSolver::outside_answers`.
+    outside_answers: bool,
+}
+
+/// Parses the value following `option`, naming it `what` in the error message.
+fn parse_value<'a, T: FromStr>(
+    iter: &mut impl Iterator<Item = &'a String>,
+    option: &str,
+    what: &str,
+) -> Result<T, String> {
+    let value = iter
+        .next()
+        .ok_or_else(|| format!("missing value for {option}"))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid {what} '{value}'"))
+}
+
+#[allow(clippy::too_many_lines)]
+fn parse_args(mode: &Mode, args: &[String]) -> Result<Option<CliOptions>, String> {
+    let mut word_length = None;
+    let mut first_char = String::new();
+    let mut top = 5;
+    let mut json = false;
+    let mut steps = Vec::new();
+    let mut language = "fr".to_string();
+    let mut dictionary = None;
+    // Layout overrides, applied to the language profile once it is known.
+    let mut separator = None;
+    let mut word_column = None;
+    let mut frequency_column = None;
+    let mut normalization = None;
+    let mut answers = AnswerFilter::default();
+    let mut solver = SolverOptions::default();
+    let mut simulation = SimulationOptions::default();
+    let mut daily = DailyOptions::new(0, "");
+    let mut date = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-j" | "--json" => json = true,
+            "-l" | "--length" => {
+                word_length = Some(parse_value(&mut iter, "--length", "word length")?);
+            }
+            "-f" | "--first" => {
+                first_char.clone_from(iter.next().ok_or("missing value for --first")?);
+            }
+            "-t" | "--top" => {
+                top = parse_value(&mut iter, "--top", "top count")?;
+            }
+            "-s" | "--scoring" => {
+                solver.scoring = iter
+                    .next()
+                    .ok_or("missing value for --scoring")?
+                    .parse::<ScoringMode>()?;
+            }
+            "--lookahead-top" => {
+                solver.lookahead_top =
+                    parse_value(&mut iter, "--lookahead-top", "lookahead count")?;
+            }
+            "--prior" => {
+                solver.prior = iter
+                    .next()
+                    .ok_or("missing value for --prior")?
+                    .parse::<Prior>()?;
+            }
+            "--hard" => solver.hard_mode = true,
+            "--sample" => {
+                simulation.sample = Some(parse_value(&mut iter, "--sample", "sample size")?);
+            }
+            "--seed" => {
+                simulation.seed = parse_value(&mut iter, "--seed", "seed")?;
+                daily.seed = simulation.seed;
+            }
+            "--max-guesses" => {
+                simulation.max_guesses = parse_value(&mut iter, "--max-guesses", "guess count")?;
+            }
+            "--opener" => {
+                simulation.opener = Some(iter.next().ok_or("missing value for --opener")?.clone());
+            }
+            "--date" => date = Some(parse_value(&mut iter, "--date", "date")?),
+            "--window" => daily.window = parse_value(&mut iter, "--window", "window")?,
+            "--min-frequency" => {
+                daily.min_frequency = parse_value(&mut iter, "--min-frequency", "frequency")?;
+            }
+            "--language" => {
+                language.clone_from(iter.next().ok_or("missing value for --language")?);
+            }
+            "-d" | "--dictionary" => {
+                dictionary = Some(PathBuf::from(
+                    iter.next().ok_or("missing value for --dictionary")?,
+                ));
+            }
+            "--separator" => {
+                let value = iter.next().ok_or("missing value for --separator")?;
+                separator = Some(
+                    value
+                        .chars()
+                        .exactly_one()
+                        .map_err(|_| format!("invalid separator '{value}'"))?,
+                );
+            }
+            "--word-column" => {
+                word_column = Some(
+                    iter.next()
+                        .ok_or("missing value for --word-column")?
+                        .clone(),
+                );
+            }
+            "--frequency-column" => {
+                frequency_column = Some(Some(
+                    iter.next()
+                        .ok_or("missing value for --frequency-column")?
+                        .clone(),
+                ));
+            }
+            "--no-frequency" => frequency_column = Some(None),
+            "--normalization" => {
+                normalization = Some(
+                    iter.next()
+                        .ok_or("missing value for --normalization")?
+                        .parse::<Normalization>()?,
+                );
+            }
+            "--answer-categories" => {
+                answers.categories = iter
+                    .next()
+                    .ok_or("missing value for --answer-categories")?
+                    .split(',')
+                    .map(|category| category.trim().to_uppercase())
+                    .filter(|category| !category.is_empty())
+                    .collect();
+            }
+            "--lemmas-only" => answers.lemmas_only = true,
+            "--singular-only" => answers.singular_only = true,
+            step => {
+                let (word, pattern) = step
+                    .split_once(':')
+                    .ok_or_else(|| format!("invalid step '{step}', expected WORD:PATTERN"))?;
+                steps.push((word.to_string(), pattern.to_string()));
+            }
+        }
+    }
+    // The openers are generated for every length, and the Lexique is copied as a whole.
+    let word_length = match (word_length, mode) {
+        (Some(word_length), _) => word_length,
+        (None, Mode::Openers | Mode::Lexique) => 0,
+        (None, _) => return Err("missing --length".to_string()),
+    };
+    // The embedded Lexique has no grammatical columns yet, the filters would always fail on it.
+    if !answers.is_empty() && dictionary.is_none() && !Dictionary::lexique().has_grammar() {
+        return Err(
+            "--answer-categories, --lemmas-only and --singular-only need a --dictionary with the grammatical columns of Lexique 3.81"
+                .to_string(),
+        );
+    }
+    let mut profile = LanguageProfile::builtin(&language).map_err(|error| error.to_string())?;
+    if dictionary.is_some() {
+        profile.path = dictionary;
+    }
+    if let Some(separator) = separator {
+        profile.format.separator = separator;
+    }
+    if let Some(word_column) = word_column {
+        profile.format.word_column = word_column;
+    }
+    if let Some(frequency_column) = frequency_column {
+        profile.format.frequency_column = frequency_column;
+    }
+    if let Some(normalization) = normalization {
+        profile.format.normalization = normalization;
+    }
+    profile.answers = answers;
+    daily.word_length = word_length;
+    daily.first_char.clone_from(&first_char);
+    Ok(Some(CliOptions {
+        word_length,
+        first_char,
+        top,
+        json,
+        steps,
+        profile,
+        solver,
+        simulation,
+        daily,
+        date,
+    }))
+}
+
+fn load_dictionary(options: &CliOptions) -> Result<Cow<'static, Dictionary>, LanguageError> {
+    options.profile.load()
+}
+
+fn build_report(dictionary: &Dictionary, options: &CliOptions) -> Result<CliReport, SolverError> {
+    let recommendations = WordleOpeners::recommendations(
+        dictionary,
+        &options.steps,
+        options.word_length,
+        &options.first_char,
+        &options.solver,
+        options.top,
+    )?;
+    Ok(CliReport {
+        recommended_count: recommendations.recommended_count,
+        possible_count: recommendations.possible_count,
+        total_entropy: WordleSolver::get_uniform_entropy(
+            recommendations
+                .recommended_count
+                .try_into()
+                .unwrap_or(i32::MAX),
+        ),
+        recommended: recommendations
+            .recommended
+            .into_iter()
+            .take(options.top)
+            .collect(),
+        possible: recommendations
+            .possible
+            .into_iter()
+            .take(options.top)
+            .collect(),
+        outside_answers: recommendations.outside_answers,
+    })
+}
+
+fn format_score(score: Option<f32>) -> String {
+    score.map_or_else(|| "-".to_string(), |score| format!("{score:.4}"))
+}
+
+fn print_table(words: &[WordleEntity]) {
+    println!(
+        "{:<16} {:>12} {:>12} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "Word",
+        "Frequency",
+        "Probability",
+        "Entropy",
+        "Two-step",
+        "Remaining",
+        "Guesses",
+        "Worst",
+        "Expected"
+    );
+    for we in words {
+        println!(
+            "{:<16} {:>12.2} {:>12.4} {:>10.4} {:>10} {:>10} {:>10.4} {:>10} {:>10.4}",
+            we.word,
+            we.frequency,
+            we.probability,
+            we.entropy,
+            format_score(we.two_step_entropy),
+            format_score(we.two_step_remaining),
+            we.expected_guesses,
+            we.largest_bucket,
+            we.expected_bucket
+        );
+    }
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect_vec();
+    let (mode, args) = match args.split_first() {
+        Some((command, rest)) if command == "play" => (Mode::Play, rest),
+        Some((command, rest)) if command == "simulate" => (Mode::Simulate, rest),
+        Some((command, rest)) if command == "daily" => (Mode::Daily, rest),
+        Some((command, rest)) if command == "strategy" => (Mode::Strategy, rest),
+        Some((command, rest)) if command == "openers" => (Mode::Openers, rest),
+        Some((command, rest)) if command == "lexique" => (Mode::Lexique, rest),
+        _ => (Mode::Report, args.as_slice()),
+    };
+    let options = match parse_args(&mode, args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    // The complete Lexique is copied line by line, not loaded as a dictionary.
+    if matches!(mode, Mode::Lexique) {
+        return match lexique::run(&options) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("error: {error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let dictionary = match load_dictionary(&options) {
+        Ok(dictionary) => dictionary,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match mode {
+        Mode::Play => play::run(&dictionary, options),
+        Mode::Simulate => simulate::run(&dictionary, &options),
+        Mode::Daily => daily::run(&dictionary, &options),
+        Mode::Strategy => strategy::run(&dictionary, &options),
+        Mode::Openers => openers::run(&dictionary, &options),
+        Mode::Report => return report(&dictionary, &options),
+        Mode::Lexique => unreachable!("the Lexique is copied before loading the dictionary"),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn report(dictionary: &Dictionary, options: &CliOptions) -> ExitCode {
+    let report = match build_report(dictionary, options) {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::from(2);
+        }
+    };
+    if options.json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{json}"),
+            Err(error) => {
+                eprintln!("error: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        println!("Recommended words");
+        println!(
+            "{} words, {} total entropy",
+            report.recommended_count, report.total_entropy
+        );
+        print_table(&report.recommended);
+        println!();
+        println!("Possible words");
+        println!("{} words", report.possible_count);
+        if report.outside_answers {
+            println!("No answer matches the steps, these words are outside of the answers");
+        }
+        print_table(&report.possible);
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,27 @@
+use super::CliOptions;
+use rustle::WordleDictionary::Dictionary;
+use rustle::WordleOpeners;
+
+pub(crate) fn run(
+    dictionary: &Dictionary,
+    options: &CliOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let openers = WordleOpeners::generate(dictionary, options.top, &options.simulation, |table| {
+        eprintln!(
+            "{} letters {}: {} words, {}",
+            table.word_length,
+            if table.first_char.is_empty() {
+                "any"
+            } else {
+                &table.first_char
+            },
+            table.words,
+            table
+                .by_entropy
+                .first()
+                .map_or("no opener", |opener| opener.word.as_str())
+        );
+    })?;
+    print!("{}", openers.to_json());
+    Ok(())
+}
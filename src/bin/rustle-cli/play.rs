@@ -0,0 +1,170 @@
+use super::CliOptions;
+use itertools::Itertools;
+use rustle::WordleDictionary::Dictionary;
+use rustle::WordleOpeners::{self, Recommendations};
+use rustle::WordleSolver;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "Commands:
+  <PATTERN>          Pattern observed for the suggested word, e.g. 01200
+  <WORD> <PATTERN>   Pattern observed for another word (WORD:PATTERN works too)
+  undo               Remove the last step
+  restart            Remove every step
+  steps              Print the steps entered so far
+  help               Print this help
+  quit               Leave the session";
+
+enum Command {
+    Step(String, String),
+    Undo,
+    Restart,
+    Steps,
+    Help,
+    Quit,
+}
+
+fn parse_command(line: &str, suggestion: Option<&str>) -> Result<Command, String> {
+    let tokens = line
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .filter(|token| !token.is_empty())
+        .collect_vec();
+    match tokens.as_slice() {
+        ["undo"] => Ok(Command::Undo),
+        ["restart"] => Ok(Command::Restart),
+        ["steps"] => Ok(Command::Steps),
+        ["help"] => Ok(Command::Help),
+        ["quit" | "exit"] => Ok(Command::Quit),
+        [pattern] => suggestion
+            .map(|word| Command::Step(word.to_string(), (*pattern).to_string()))
+            .ok_or_else(|| "no suggested word, enter <WORD> <PATTERN>".to_string()),
+        [word, pattern] => Ok(Command::Step((*word).to_string(), (*pattern).to_string())),
+        _ => Err("unrecognized input, type `help` for the list of commands".to_string()),
+    }
+}
+
+fn print_turn(steps: &[(String, String)], recommendations: &Recommendations, top: usize) {
+    let candidates = recommendations.possible_count;
+    println!();
+    println!(
+        "Turn {}: {} candidates, {} bits of entropy remaining",
+        steps.len() + 1,
+        candidates,
+        WordleSolver::get_uniform_entropy(candidates.try_into().unwrap_or(i32::MAX))
+    );
+    if recommendations.outside_answers {
+        println!("No answer matches the steps, these words are outside of the answers");
+    }
+    if candidates > 0 {
+        println!(
+            "Most frequent candidates: {}",
+            recommendations
+                .possible
+                .iter()
+                .take(top)
+                .map(|we| &we.word)
+                .join(", ")
+        );
+    }
+}
+
+pub(crate) fn run(
+    dictionary: &Dictionary,
+    options: CliOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let CliOptions {
+        word_length,
+        first_char,
+        top,
+        mut steps,
+        solver,
+        ..
+    } = options;
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    println!("{HELP}");
+
+    loop {
+        let recommendations = WordleOpeners::recommendations(
+            dictionary,
+            &steps,
+            word_length,
+            &first_char,
+            &solver,
+            top,
+        )?;
+
+        print_turn(&steps, &recommendations, top);
+        // The openers hold more candidates than `suggested_guess` finishes with.
+        let suggestion = if recommendations.precomputed {
+            recommendations.recommended.first()
+        } else {
+            WordleSolver::suggested_guess(&recommendations.recommended)
+        }
+        .map(|we| we.word.clone());
+        match &suggestion {
+            Some(word) => print!("Suggested guess: {word}\n> "),
+            None => print!("No word left, `undo` the last step or `restart`\n> "),
+        }
+        io::stdout().flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            return Ok(());
+        };
+        let command = match parse_command(&line, suggestion.as_deref()) {
+            Ok(command) => command,
+            Err(message) => {
+                println!("{message}");
+                continue;
+            }
+        };
+        match command {
+            Command::Step(word, pattern) => {
+                let word = dictionary.normalize(&word);
+                let rule = match WordleSolver::parse_step(&word, &pattern, word_length) {
+                    Ok(rule) => rule,
+                    Err(error) => {
+                        println!("{error}");
+                        continue;
+                    }
+                };
+                if pattern.chars().all(|c| c == '2') {
+                    println!("Solved in {} guesses!", steps.len() + 1);
+                    return Ok(());
+                }
+                // Before the first step every answer is a candidate, the openers hold only a few.
+                let remaining = if recommendations.precomputed {
+                    dictionary
+                        .words(word_length, &first_char)
+                        .iter()
+                        .filter(|(word, _)| {
+                            dictionary.is_answer(word) && rule.Is_Word_Conform(word)
+                        })
+                        .count()
+                } else {
+                    recommendations
+                        .possible
+                        .iter()
+                        .filter(|we| rule.Is_Word_Conform(&we.word))
+                        .count()
+                };
+                if remaining == 0 {
+                    println!("No candidate matches {word}:{pattern}, check the pattern or `undo`");
+                }
+                steps.push((word, pattern));
+            }
+            Command::Undo => {
+                if steps.pop().is_none() {
+                    println!("Nothing to undo");
+                }
+            }
+            Command::Restart => steps.clear(),
+            Command::Steps => {
+                for (word, pattern) in &steps {
+                    println!("{word}:{pattern}");
+                }
+            }
+            Command::Help => println!("{HELP}"),
+            Command::Quit => return Ok(()),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use super::CliOptions;
+use itertools::Itertools;
+use rustle::WordleDictionary::Dictionary;
+use rustle::WordleSimulation::{self, SimulationOptions};
+
+pub(crate) fn run(
+    dictionary: &Dictionary,
+    options: &CliOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let simulation = SimulationOptions {
+        solver: options.solver.clone(),
+        ..options.simulation.clone()
+    };
+    let report = WordleSimulation::simulate(
+        dictionary,
+        options.word_length,
+        &options.first_char,
+        &simulation,
+    )?;
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} games, {:.4} guesses on average",
+        report.games.len(),
+        report.average_guesses()
+    );
+    for (guess_count, games) in report.distribution() {
+        println!("{guess_count:>3} guesses: {games}");
+    }
+    let failures = report.failures();
+    println!(
+        "{} games needed more than {} guesses",
+        failures.len(),
+        report.max_guesses
+    );
+    println!("Worst words");
+    for game in report.worst_games(options.top) {
+        println!("{:<16} {}", game.answer, game.guesses.iter().join(" "));
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use super::CliOptions;
+use rustle::WordleDictionary::Dictionary;
+use rustle::WordleStrategy::{self, Strategy};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct StrategyReport {
+    answers: usize,
+    depth: usize,
+    average_guesses: f32,
+    distribution: BTreeMap<usize, usize>,
+    strategy: Strategy,
+}
+
+pub(crate) fn run(
+    dictionary: &Dictionary,
+    options: &CliOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let strategy = WordleStrategy::build_strategy(
+        dictionary,
+        options.word_length,
+        &options.first_char,
+        &options.solver,
+    )?;
+    if options.json {
+        let report = StrategyReport {
+            answers: strategy.answers(),
+            depth: strategy.depth(),
+            average_guesses: strategy.average_guesses(),
+            distribution: strategy.distribution(),
+            strategy,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} answers, {:.4} guesses on average, {} at most",
+        strategy.answers(),
+        strategy.average_guesses(),
+        strategy.depth()
+    );
+    print!("{}", strategy.to_text());
+    Ok(())
+}
@@ -0,0 +1,16 @@
+#![allow(non_snake_case)]
+#![warn(clippy::pedantic)]
+pub mod WordleAdversary;
+pub mod WordleAnalysis;
+pub mod WordleDaily;
+pub mod WordleDictionary;
+#[cfg(test)]
+mod WordleFixtures;
+pub mod WordleGame;
+pub mod WordleLanguage;
+pub mod WordleMultiBoard;
+pub mod WordleOpeners;
+pub mod WordleShare;
+pub mod WordleSimulation;
+pub mod WordleSolver;
+pub mod WordleStrategy;
@@ -1,106 +1,869 @@
 #![allow(non_snake_case)]
 #![warn(clippy::pedantic)]
-mod WordleSolver;
 use dioxus::prelude::*;
 use itertools::Itertools;
-use WordleSolver::WordleEntity;
+use rustle::WordleAdversary::AdversarialGame;
+use rustle::WordleAnalysis;
+use rustle::WordleDaily::DailyOptions;
+use rustle::WordleDictionary::{AnswerFilter, Dictionary};
+use rustle::WordleGame::{Game, GameOptions, GameState};
+use rustle::WordleLanguage::{LanguageProfile, LANGUAGE_CODES};
+use rustle::WordleMultiBoard::{self, BOARD_COUNTS};
+use rustle::WordleOpeners;
+use rustle::WordleShare::{self, EmojiStyle};
+use rustle::WordleSolver::{self, Pattern, Prior, ScoringMode, SolverOptions, WordleEntity};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::rc::Rc;
 
 fn main() {
+    // Parse the dictionary once, before any session asks for recommendations.
+    Dictionary::lexique();
     launch(app);
 }
 #[derive(Clone)]
 pub struct WordleStartEntity {
-    pub steps:Vec<(String,String)>,
+    /// Steps of each board guessed together, a single one for Wordle.
+    pub boards:Vec<Vec<(String,String)>>,
     pub word_length: usize,
     pub first_char: String,
+    pub options: SolverOptions,
 }
 
 fn app() -> Element {
-    let rec = use_signal(|| WordleStartEntity{steps:Vec::new(),first_char:String::new(),word_length:0});
+    let rec = use_signal(|| WordleStartEntity{boards:vec![Vec::new()],first_char:String::new(),word_length:0,options:SolverOptions::default()});
+    let mut view = use_signal(|| View::Solver);
+    let mut palette = use_context_provider(|| Signal::new(Palette::Wordle));
+    use_context_provider(|| Signal::new(SessionDictionary::new(Cow::Borrowed(Dictionary::lexique()))));
     
     rsx! {
         div {
             link { href:"https://cdn.jsdelivr.net/npm/bootstrap/dist/css/bootstrap.css", rel:"stylesheet" }
             h1 { "Welcome to rustle !" }
-            WordleInput{rec}
-            Recommendations{rec}
+            LanguagePicker{}
+            ul { class:"nav nav-tabs",
+                for (tab, title) in [(View::Solver, "Solver"), (View::Game, "Play"), (View::Adversarial, "Absurdle"), (View::Analysis, "Analysis")] {
+                    li { class:"nav-item",
+                        a { class: if view() == tab { "nav-link active" } else { "nav-link" }, href:"#",
+                            onclick: move |_| view.set(tab), "{title}" }
+                    }
+                }
+            }
+            select { class:"form-select",
+                onchange: move |event| palette.set(if event.value() == "sutom" { Palette::Sutom } else { Palette::Wordle }),
+                option { value:"wordle", selected: palette() == Palette::Wordle, "Wordle colors" }
+                option { value:"sutom", selected: palette() == Palette::Sutom, "Sutom colors" }
+            }
+            match view() {
+                View::Solver => rsx! {
+                    WordleInput{rec}
+                    StepHistory{rec}
+                    SharePanel{rec}
+                    Recommendations{rec}
+                },
+                View::Game => rsx! { GameView{} },
+                View::Adversarial => rsx! { AdversarialView{} },
+                View::Analysis => rsx! { AnalysisView{} },
+            }
        }
     }
 }
 
+/// Language of the words, the profiles other than French read one of the word lists of the server.
+/// The answers can be restricted once words with the grammatical columns of a complete Lexique
+/// are loaded, the filters are hidden before as they could only fail.
+#[component]
+fn LanguagePicker() -> Element {
+    let mut dictionary = use_context::<Signal<SessionDictionary>>();
+    let mut error = use_signal(|| None::<String>);
+    let mut lemmas_only = use_signal(|| false);
+    let mut singular_only = use_signal(|| false);
+    let lists = word_lists();
+    let grammar = dictionary.read().has_grammar();
+    rsx! {
+        form {
+            onsubmit: move |event| {
+                let values = event.data.values();
+                let list = values["Word List"].as_value();
+                let loaded = LanguageProfile::builtin(&values["Language"].as_value()).and_then(|mut profile| {
+                    // Lists are picked by name among the files of the directory, never by path.
+                    profile.path = word_lists().into_iter().find(|(name, _)| *name == list).map(|(_, path)| path);
+                    if grammar {
+                        profile.answers = AnswerFilter {
+                            categories: values["Answer Categories"].as_value().split(',').map(|category| category.trim().to_uppercase()).filter(|category| !category.is_empty()).collect(),
+                            lemmas_only: lemmas_only(),
+                            singular_only: singular_only(),
+                        };
+                    }
+                    profile.load()
+                });
+                match loaded {
+                    Ok(loaded) => {
+                        error.set(None);
+                        dictionary.set(Rc::new(loaded));
+                    }
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            },
+            select { class:"form-select", name: "Language",
+                for code in LANGUAGE_CODES {
+                    option { value: code,
+                        {LanguageProfile::builtin(code).map(|profile| profile.name).unwrap_or_default()}
+                        if code != "fr" { " (a word list of the server)" }
+                    }
+                }
+            }
+            select { class:"form-select", name: "Word List",
+                option { value: "", "No word list, French uses Lexique" }
+                for (name, _) in lists {
+                    option { value: "{name}", "{name}" }
+                }
+            }
+            if grammar {
+                input { class:"form-control", name: "Answer Categories",placeholder: "Answer categories, e.g. NOM,ADJ" }
+                div { class:"form-check",
+                    input { class:"form-check-input", r#type:"checkbox", id:"lemmas-only", checked: lemmas_only(),
+                        onchange: move |event| lemmas_only.set(event.value() == "true") }
+                    label { class:"form-check-label", r#for:"lemmas-only", "No inflected form as the answer" }
+                }
+                div { class:"form-check",
+                    input { class:"form-check-input", r#type:"checkbox", id:"singular-only", checked: singular_only(),
+                        onchange: move |event| singular_only.set(event.value() == "true") }
+                    label { class:"form-check-label", r#for:"singular-only", "No plural form as the answer" }
+                }
+            } else {
+                small { class:"form-text text-muted", "The answers can be restricted once a word list with the grammatical columns of Lexique 3.81 is loaded" }
+            }
+            input { class:"btn btn-secondary", r#type: "submit", value: "Load words" }
+        }
+        if let Some(message) = error() {
+            div { class:"invalid-feedback d-block", "{message}" }
+        }
+    }
+}
+
+/// Words of a session: the embedded Lexique, or a word list read for the session and dropped with it.
+type SessionDictionary = Rc<Cow<'static, Dictionary>>;
+
+/// Directory of the word lists sessions may read, none when it is not set.
+const WORD_LISTS_VAR: &str = "RUSTLE_WORD_LISTS";
+
+/// Names and paths of the files of the `RUSTLE_WORD_LISTS` directory, sorted by name.
+fn word_lists() -> Vec<(String, PathBuf)> {
+    let Some(directory) = std::env::var_os(WORD_LISTS_VAR) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| Some((entry.file_name().into_string().ok()?, entry.path())))
+        .sorted()
+        .collect()
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum View {
+    Solver,
+    Game,
+    Adversarial,
+    Analysis,
+}
+
 #[component]
 fn WordleInput(rec: Signal<WordleStartEntity>) -> Element {
+    let dictionary = use_context::<Signal<SessionDictionary>>();
+    let mut error = use_signal(|| None::<String>);
+    let mut word = use_signal(String::new);
+    // Pattern of the word on each board, all incorrect until its tiles are clicked.
+    let mut patterns = use_signal(Vec::<Vec<Pattern>>::new);
     rsx! {
         form {
             onsubmit: move |event| {
-                rec.set(WordleStartEntity{
-                    first_char:event.data.values()["First Char"].as_value(),
-                    word_length:event.data.values()["Word Length"].as_value().parse().unwrap(),
-                    steps:vec![]})
+                match event.data.values()["Word Length"].as_value().trim().parse() {
+                    Ok(word_length) => {
+                        error.set(None);
+                        let options = rec.read().options.clone();
+                        let board_count = event.data.values()["Boards"].as_value().parse().unwrap_or(1);
+                        rec.set(WordleStartEntity{
+                            first_char:event.data.values()["First Char"].as_value(),
+                            word_length,
+                            boards:vec![Vec::new(); board_count],
+                            options});
+                        word.set(String::new());
+                        patterns.set(Vec::new());
+                    }
+                    Err(_) => error.set(Some("Word Length should be a number".to_string())),
+                }
             },
             input { class:"form-control", name: "Word Length",placeholder: "Word Length" }
             input { class:"form-control", name: "First Char",placeholder: "First Char" }
+            select { class:"form-select", name: "Boards",
+                for count in BOARD_COUNTS {
+                    option { value:"{count}", selected: rec.read().boards.len() == count, "{count} board(s)" }
+                }
+            }
             input { class:"btn btn-primary", r#type: "submit" }
         }    
+        select { class:"form-select",
+            onchange: move |event| rec.write().options.scoring = event.value().parse().unwrap_or_default(),
+            for mode in ScoringMode::ALL {
+                option { value:"{mode.name()}", selected: rec.read().options.scoring == mode, "Rank by {mode.name()}" }
+            }
+        }
+        div { class:"form-check",
+            input { class:"form-check-input", r#type:"checkbox", id:"hard-mode", checked: rec.read().options.hard_mode,
+                onchange: move |event| rec.write().options.hard_mode = event.value() == "true" }
+            label { class:"form-check-label", r#for:"hard-mode", "Hard mode: only guess words matching every step" }
+        }
+        select { class:"form-select",
+            onchange: move |event| rec.write().options.prior = event.value().parse().unwrap_or_default(),
+            option { value:"{Prior::Uniform}", selected: rec.read().options.prior == Prior::Uniform, "Every candidate equally likely" }
+            option { value:"{Prior::DEFAULT_SIGMOID}", selected: rec.read().options.prior == Prior::DEFAULT_SIGMOID, "Frequent candidates more likely" }
+        }
         if rec.read().word_length != 0{
         {
             rsx!{
                 form {
-                    onsubmit: move |event| {
-                        rec.write().steps.push((event.data.values()["Word"].as_value(), event.data.values()["Pattern"].as_value()));
-                        rec.set(rec.cloned())
+                    onsubmit: move |_| {
+                        let guess = dictionary().normalize(word().trim());
+                        let word_length = rec.read().word_length;
+                        // Solved boards get no tiles and keep their steps.
+                        let steps = rec.read().boards.iter().enumerate()
+                            .map(|(board, steps)| (!WordleMultiBoard::is_solved(steps))
+                                .then(|| (guess.clone(), WordleSolver::format_pattern(&board_pattern(&patterns.read(), board, word_length)))))
+                            .collect_vec();
+                        let parsed = steps.iter().flatten()
+                            .map(|(guess, pattern)| WordleSolver::parse_step(guess, pattern, word_length))
+                            .collect::<Result<Vec<_>, _>>();
+                        match parsed {
+                            Ok(_) => {
+                                error.set(None);
+                                for (board, step) in rec.write().boards.iter_mut().zip(steps) {
+                                    board.extend(step);
+                                }
+                                rec.set(rec.cloned());
+                                word.set(String::new());
+                                patterns.set(Vec::new());
+                            }
+                            Err(e) => error.set(Some(e.to_string())),
+                        }
                     },
-                    input { class:"form-control", name: "Word",placeholder: "Word"}
-                    input { class:"form-control", name: "Pattern",placeholder: "Pattern" }
-                    small { class:"form-text text-muted", "0/1/2 => Incorrect/Misplaced/Correct" }
+                    input { class:"form-control", name: "Word",placeholder: "Word", value: "{word}",
+                        oninput: move |event| word.set(event.value()) }
+                    for (board, steps) in rec.read().boards.iter().enumerate() {
+                        if !WordleMultiBoard::is_solved(steps) {
+                            TileInput{word:word(), pattern:board_pattern(&patterns.read(), board, rec.read().word_length),
+                                on_toggle: move |position: usize| {
+                                    let word_length = rec.read().word_length;
+                                    let board_count = rec.read().boards.len();
+                                    let mut patterns = patterns.write();
+                                    patterns.resize(board_count, Vec::new());
+                                    let pattern = &mut patterns[board];
+                                    pattern.resize(word_length, Pattern::Incorrect);
+                                    pattern[position] = next_pattern(pattern[position]);
+                                }}
+                        }
+                    }
+                    small { class:"form-text text-muted", "Click a tile to change its color" }
                     input { class:"btn btn-primary", r#type: "submit" }
                 }
             }
         }        
         }
+        if let Some(message) = error() {
+            div { class:"invalid-feedback d-block", "{message}" }
+        }
+    }
+}
+
+/// Every step entered so far, whose tiles can be clicked to fix a pattern.
+#[component]
+fn StepHistory(rec: Signal<WordleStartEntity>) -> Element {
+    let dictionary = use_context::<Signal<SessionDictionary>>();
+    let mut error = use_signal(|| None::<String>);
+    if rec.read().boards.iter().all(Vec::is_empty) {
+        return None;
+    }
+    let boards = rec.read().boards.clone();
+    let board_count = boards.len();
+    rsx! {
+        h3 { "Steps" }
+        div { class:"row",
+            for (board, steps) in boards.into_iter().enumerate() {
+                div { class:"col",
+                    if board_count > 1 {
+                        h5 { "Board {board + 1}" }
+                    }
+                    for (index, (word, pattern)) in steps.into_iter().enumerate() {
+                        div { class:"d-flex gap-2 align-items-center",
+                            TileInput{word:word.clone(), pattern:WordleSolver::parse_pattern(&pattern).unwrap_or_default(),
+                                on_toggle: move |position: usize| {
+                                    let mut se = rec.write();
+                                    let steps = &mut se.boards[board];
+                                    let mut tiles = WordleSolver::parse_pattern(&steps[index].1).unwrap_or_default();
+                                    if let Some(tile) = tiles.get_mut(position) {
+                                        *tile = next_pattern(*tile);
+                                    }
+                                    // Solving or unsolving a board would leave it out of step with the others.
+                                    if WordleMultiBoard::replace_pattern(steps, index, &WordleSolver::format_pattern(&tiles)) {
+                                        error.set(None);
+                                    } else {
+                                        error.set(Some("A board is only solved or unsolved by adding or deleting a guess".to_string()));
+                                    }
+                                }}
+                            // Only words fitting the patterns replace the guessed word, on every board.
+                            input { class:"form-control form-control-sm w-auto", value:"{word}",
+                                onchange: move |event| {
+                                    let word_length = rec.read().word_length;
+                                    match WordleMultiBoard::replace_guess(&dictionary(), &mut rec.write().boards, index, &event.value(), word_length) {
+                                        Ok(()) => error.set(None),
+                                        Err(e) => error.set(Some(e.to_string())),
+                                    }
+                                } }
+                            button { r#type:"button", class:"btn btn-sm btn-outline-danger",
+                                onclick: move |_| WordleMultiBoard::remove_guess(&mut rec.write().boards, index),
+                                "Delete" }
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(message) = error() {
+            div { class:"invalid-feedback d-block", "{message}" }
+        }
+        button { r#type:"button", class:"btn btn-outline-secondary",
+            onclick: move |_| rec.write().boards.iter_mut().for_each(Vec::clear),
+            "Reset" }
+    }
+}
+
+/// Imports the steps of a shared emoji grid and exports the current steps as one.
+#[component]
+fn SharePanel(rec: Signal<WordleStartEntity>) -> Element {
+    let palette = use_context::<Signal<Palette>>();
+    let mut error = use_signal(|| None::<String>);
+    let style = match palette() {
+        Palette::Wordle => EmojiStyle::Wordle,
+        Palette::Sutom => EmojiStyle::Sutom,
+    };
+    let grids = rec.read().boards.iter()
+        .filter(|steps| !steps.is_empty())
+        .map(|steps| WordleShare::to_emoji_grid(steps, style).unwrap_or_default())
+        .collect_vec();
+    rsx! {
+        details {
+            summary { "Share" }
+            for grid in grids {
+                pre { "{grid}" }
+            }
+            form {
+                onsubmit: move |event| {
+                    let values = event.data.values();
+                    let words = values["Words"].as_value().split(|c: char| c.is_whitespace() || c == ',')
+                        .filter(|word| !word.is_empty())
+                        .map(str::to_string)
+                        .collect_vec();
+                    if rec.read().boards.len() != 1 {
+                        error.set(Some("Grids can only be imported with a single board".to_string()));
+                        return;
+                    }
+                    match WordleShare::parse_emoji_grid(&values["Grid"].as_value(), &words) {
+                        Ok(steps) => {
+                            let word_length = steps.first().map_or(rec.read().word_length, |(word, _)| word.chars().count());
+                            error.set(None);
+                            let mut se = rec.write();
+                            se.word_length = word_length;
+                            se.boards = vec![steps];
+                        }
+                        Err(e) => error.set(Some(e.to_string())),
+                    }
+                },
+                textarea { class:"form-control", name: "Grid", placeholder: "Pasted grid" }
+                input { class:"form-control", name: "Words", placeholder: "Guessed words, in order" }
+                input { class:"btn btn-primary", r#type: "submit", value: "Import" }
+            }
+            if let Some(message) = error() {
+                div { class:"invalid-feedback d-block", "{message}" }
+            }
+        }
     }
 }
 
 #[component]
 fn Recommendations(rec: ReadOnlySignal<WordleStartEntity>) -> Element {
+    let dictionary = use_context::<Signal<SessionDictionary>>()();
+    // The precomputed openers are only the top words, sorting a column scores every word.
+    let mut sorted = use_signal(|| false);
     let se = rec.read();
-    let mut recommended = WordleSolver::retrieve_recommended_words(&se.steps, se.word_length, &se.first_char);
-    recommended.sort_by(|a, b| b.entropy.partial_cmp(&a.entropy).unwrap());
-    let mut possible = recommended
-        .iter()
-        .filter(|entity| entity.is_candidate)
-        .cloned()
-        .collect_vec();
-    possible.sort_by(|a, b| b.frequency.partial_cmp(&a.frequency).unwrap());
+    if se.boards.len() > 1 {
+        return rsx! { MultiBoardRecommendations{rec} };
+    }
+    let result = if sorted() {
+        WordleOpeners::scored_recommendations(&dictionary, &se.boards[0], se.word_length, &se.first_char, &se.options)
+    } else {
+        WordleOpeners::recommendations(&dictionary, &se.boards[0], se.word_length, &se.first_char, &se.options, 5)
+    };
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => return rsx! { div { class:"alert alert-danger", "{e}" } },
+    };
     rsx! {
         h3 { "Recommended words" }
-        label{"{recommended.len()} words, {WordleSolver::get_uniform_entropy(recommended.len().try_into().unwrap())} total entropy"}
-        WordleTable{words:recommended, size:5}
+        if result.precomputed {
+            label{"Precomputed openers among {result.recommended_count} words"}
+        } else {
+            label{"{result.recommended_count} words, {WordleSolver::get_uniform_entropy(result.recommended_count.try_into().unwrap())} total entropy"}
+        }
+        WordleTable{words:result.recommended, size:5, on_sort: move |()| sorted.set(true)}
         h3 { "Possible words" }
-        label{"{possible.len()} words"}
-        WordleTable{words:possible, size:5}
+        label{"{result.possible_count} words"}
+        if result.outside_answers {
+            div { class:"alert alert-warning", "No answer matches the steps, these words are outside of the answers" }
+        }
+        WordleTable{words:result.possible, size:5, on_sort: move |()| sorted.set(true)}
     }
 }
 
 #[component]
-fn WordleTable(words: Vec<WordleEntity>, size : usize) -> Element {
+fn MultiBoardRecommendations(rec: ReadOnlySignal<WordleStartEntity>) -> Element {
+    let dictionary = use_context::<Signal<SessionDictionary>>()();
+    let se = rec.read();
+    let result = match WordleMultiBoard::retrieve_multi_board_recommendations(&dictionary, &se.boards, se.word_length, &se.first_char, &se.options) {
+        Ok(result) => result,
+        Err(e) => return rsx! { div { class:"alert alert-danger", "{e}" } },
+    };
     rsx! {
-        table { class :"table", thead {
+        h3 { "Recommended words" }
+        label{"{result.recommended.len()} words, ranked by {se.options.scoring.name()} over the unsolved boards"}
+        WordleTable{words:result.recommended, size:5}
+        div { class:"row",
+            for (index, board) in result.boards.into_iter().enumerate() {
+                div { class:"col",
+                    h4 { "Board {index + 1}" }
+                    if board.solved {
+                        label{"Solved"}
+                    } else {
+                        label{"{board.candidates.len()} possible words"}
+                        if board.outside_answers {
+                            div { class:"alert alert-warning", "No answer matches the steps, these words are outside of the answers" }
+                        }
+                        PossibleWords{words:board.candidates, size:5}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Possible words of a board, most frequent first.
+#[component]
+fn PossibleWords(words: Vec<WordleEntity>, size : usize) -> Element {
+    let mut words = words;
+    words.sort_by(|a, b| b.frequency.total_cmp(&a.frequency));
+    rsx! {
+        table { class :"table table-sm", thead {
         tr {
             th {"Word" }
-            th {"Frequency" }
-            th {"Entropy" }
+            th {"Probability" }
         }
         {
             words.iter().take(size).map(|we| {
                 rsx!{
                     tr {
                         td {"{we.word}" }
-                        td {"{we.frequency}" }
-                        td {"{we.entropy}" }
+                        td {"{we.probability}" }
+                    }
+                }
+            })
+        }}}
+    }
+}
+
+/// A game against a secret word, with the solver recommendations a click away.
+#[component]
+fn GameView() -> Element {
+    let dictionary = use_context::<Signal<SessionDictionary>>();
+    let mut game = use_signal(|| None::<Game>);
+    let mut hints = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    rsx! {
+        form {
+            onsubmit: move |event| {
+                let values = event.data.values();
+                let Ok(word_length) = values["Word Length"].as_value().trim().parse() else {
+                    error.set(Some("Word Length should be a number".to_string()));
+                    return;
+                };
+                let first_char = values["First Char"].as_value();
+                let started = if values["Secret"].as_value() == "daily" {
+                    Game::daily(&dictionary(), &DailyOptions::new(word_length, &first_char), chrono::Utc::now().date_naive())
+                } else {
+                    Game::random(&dictionary(), GameOptions::new(word_length, &first_char))
+                };
+                match started {
+                    Ok(started) => {
+                        error.set(None);
+                        game.set(Some(started));
+                    }
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            },
+            input { class:"form-control", name: "Word Length",placeholder: "Word Length" }
+            input { class:"form-control", name: "First Char",placeholder: "First Char" }
+            select { class:"form-select", name: "Secret",
+                option { value:"daily", "Word of the day" }
+                option { value:"random", "Random word" }
+            }
+            input { class:"btn btn-primary", r#type: "submit", value: "New game" }
+        }
+        if let Some(current) = game() {
+            for (word, pattern) in current.guesses() {
+                PatternTiles{word:word.clone(), pattern:pattern.clone()}
+            }
+            for _ in current.guesses().len()..current.options().max_guesses {
+                EmptyTiles{length:current.options().word_length}
+            }
+            match current.state() {
+                GameState::Won => rsx! { div { class:"alert alert-success", "Found in {current.guesses().len()} guesses!" } },
+                GameState::Lost => rsx! { div { class:"alert alert-danger", "The word was {current.secret().unwrap_or_default()}" } },
+                GameState::InProgress => rsx! {
+                    form {
+                        onsubmit: move |event| {
+                            let word = event.data.values()["Guess"].as_value();
+                            let result = game.write().as_mut().map(|game| game.guess(&dictionary(), &word));
+                            match result {
+                                Some(Err(e)) => error.set(Some(e.to_string())),
+                                _ => error.set(None),
+                            }
+                        },
+                        input { class:"form-control", name: "Guess",placeholder: "Guess" }
+                        input { class:"btn btn-primary", r#type: "submit" }
+                    }
+                },
+            }
+            div { class:"form-check",
+                input { class:"form-check-input", r#type:"checkbox", id:"hints", checked: hints(),
+                    onchange: move |event| hints.set(event.value() == "true") }
+                label { class:"form-check-label", r#for:"hints", "Show the solver hints" }
+            }
+            if hints() && current.state() == GameState::InProgress {
+                Hints{game:current.clone()}
+            }
+            if let Some(secret) = current.secret() {
+                AnalysisTable{guesses:current.guesses().iter().map(|(word, _)| word.clone()).collect_vec(), answer:secret.to_string(), first_char:current.options().first_char.clone()}
+            }
+        }
+        if let Some(message) = error() {
+            div { class:"invalid-feedback d-block", "{message}" }
+        }
+    }
+}
+
+#[component]
+fn Hints(game: Game) -> Element {
+    let dictionary = use_context::<Signal<SessionDictionary>>()();
+    let mut sorted = use_signal(|| false);
+    let options = game.options();
+    let result = if sorted() {
+        WordleOpeners::scored_recommendations(&dictionary, &game.steps(), options.word_length, &options.first_char, &SolverOptions::default())
+    } else {
+        WordleOpeners::recommendations(&dictionary, &game.steps(), options.word_length, &options.first_char, &SolverOptions::default(), 5)
+    };
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => return rsx! { div { class:"alert alert-danger", "{e}" } },
+    };
+    rsx! {
+        h3 { "Hints" }
+        label{"{result.possible_count} possible words"}
+        WordleTable{words:result.recommended, size:5, on_sort: move |()| sorted.set(true)}
+    }
+}
+
+/// Post-game report of pasted guesses against the answer.
+#[component]
+fn AnalysisView() -> Element {
+    let mut game = use_signal(|| None::<(Vec<String>, String, String)>);
+    rsx! {
+        form {
+            onsubmit: move |event| {
+                let values = event.data.values();
+                let guesses = values["Guesses"].as_value().split(|c: char| !c.is_alphabetic()).filter(|word| !word.is_empty()).map(str::to_string).collect_vec();
+                game.set(Some((guesses, values["Answer"].as_value(), values["First Char"].as_value())));
+            },
+            textarea { class:"form-control", name: "Guesses", placeholder: "Guesses, one per line" }
+            input { class:"form-control", name: "Answer",placeholder: "Answer" }
+            input { class:"form-control", name: "First Char",placeholder: "First Char" }
+            input { class:"btn btn-primary", r#type: "submit", value: "Analyse" }
+        }
+        if let Some((guesses, answer, first_char)) = game() {
+            AnalysisTable{guesses, answer, first_char}
+        }
+    }
+}
+
+/// Each guess graded against the solver: bits gained, expected bits and the solver's own guess.
+#[component]
+fn AnalysisTable(guesses: Vec<String>, answer: String, first_char: String) -> Element {
+    let dictionary = use_context::<Signal<SessionDictionary>>()();
+    let turns = match WordleAnalysis::analyse_game(&dictionary, &guesses, &answer, &first_char, &SolverOptions::default()) {
+        Ok(turns) => turns,
+        Err(e) => return rsx! { div { class:"alert alert-danger", "{e}" } },
+    };
+    rsx! {
+        h3 { "Analysis" }
+        table { class:"table",
+            thead {
+                tr {
+                    th { "Guess" }
+                    th { "Candidates" }
+                    th { "Bits" }
+                    th { "Expected" }
+                    th { "Luck" }
+                    th { "Solver guess" }
+                    th { "Solver entropy" }
+                }
+            }
+            tbody {
+                for turn in turns {
+                    tr {
+                        td { PatternTiles{word:turn.guess.clone(), pattern:WordleSolver::parse_pattern(&turn.pattern).unwrap_or_default()} }
+                        td { "{turn.candidates_before} → {turn.candidates_after}" }
+                        td { "{turn.actual_bits:.2}" }
+                        td { "{turn.expected_entropy:.2}" }
+                        td { class: if turn.luck < 0.0 { "text-danger" } else { "text-success" }, "{turn.luck:+.2}" }
+                        td { "{turn.best_guess}" }
+                        td { "{turn.best_entropy:.2}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Absurdle: the host keeps the most candidates it can after each guess.
+#[component]
+fn AdversarialView() -> Element {
+    let dictionary = use_context::<Signal<SessionDictionary>>()();
+    let mut game = use_signal(|| None::<AdversarialGame>);
+    let mut error = use_signal(|| None::<String>);
+    rsx! {
+        form {
+            onsubmit: move |event| {
+                match event.data.values()["Word Length"].as_value().trim().parse() {
+                    Ok(word_length) => match AdversarialGame::new(&dictionary, word_length, &event.data.values()["First Char"].as_value()) {
+                        Ok(started) => {
+                            error.set(None);
+                            game.set(Some(started));
+                        }
+                        Err(e) => error.set(Some(e.to_string())),
+                    },
+                    Err(_) => error.set(Some("Word Length should be a number".to_string())),
+                }
+            },
+            input { class:"form-control", name: "Word Length",placeholder: "Word Length" }
+            input { class:"form-control", name: "First Char",placeholder: "First Char" }
+            input { class:"btn btn-primary", r#type: "submit", value: "New game" }
+        }
+        if let Some(current) = game() {
+            for (word, pattern) in current.steps() {
+                PatternTiles{word:word.clone(), pattern:pattern.clone()}
+            }
+            if current.is_won() {
+                div { class:"alert alert-success", "Found in {current.steps().len()} guesses!" }
+            } else {
+                label{"{current.candidates().len()} words left"}
+                form {
+                    onsubmit: move |event| {
+                        let word = event.data.values()["Guess"].as_value();
+                        let result = game.write().as_mut().map(|game| game.guess(&word));
+                        match result {
+                            Some(Err(e)) => error.set(Some(e.to_string())),
+                            _ => error.set(None),
+                        }
+                    },
+                    input { class:"form-control", name: "Guess",placeholder: "Guess" }
+                    input { class:"btn btn-primary", r#type: "submit" }
+                }
+            }
+        }
+        if let Some(message) = error() {
+            div { class:"invalid-feedback d-block", "{message}" }
+        }
+    }
+}
+
+/// A guessed word with each letter colored by its pattern.
+#[component]
+fn PatternTiles(word: String, pattern: Vec<Pattern>) -> Element {
+    let palette = use_context::<Signal<Palette>>();
+    rsx! {
+        div { class:"d-flex gap-1 mb-1",
+            for (letter, tile) in word.chars().zip(pattern) {
+                span { class:"badge fs-4 text-uppercase {palette().class(tile)}", "{letter}" }
+            }
+        }
+    }
+}
+
+/// Tiles of the word being entered, each click moving a tile to the next color.
+#[component]
+fn TileInput(word: String, pattern: Vec<Pattern>, on_toggle: EventHandler<usize>) -> Element {
+    let palette = use_context::<Signal<Palette>>();
+    rsx! {
+        div { class:"d-flex gap-1 mb-1",
+            for (position, tile) in pattern.into_iter().enumerate() {
+                button { r#type:"button", class:"btn badge fs-4 text-uppercase {palette().class(tile)}",
+                    onclick: move |_| on_toggle.call(position),
+                    "{word.chars().nth(position).unwrap_or('\u{a0}')}"
+                }
+            }
+        }
+    }
+}
+
+fn board_pattern(patterns: &[Vec<Pattern>], board: usize, word_length: usize) -> Vec<Pattern> {
+    let mut pattern = patterns.get(board).cloned().unwrap_or_default();
+    pattern.resize(word_length, Pattern::Incorrect);
+    pattern
+}
+
+fn next_pattern(pattern: Pattern) -> Pattern {
+    match pattern {
+        Pattern::Incorrect => Pattern::Misplaced,
+        Pattern::Misplaced => Pattern::Correct,
+        Pattern::Correct => Pattern::Incorrect,
+    }
+}
+
+/// Tile colors: grey/yellow/green like Wordle or blue/yellow/red like Sutom.
+#[derive(PartialEq, Clone, Copy)]
+enum Palette {
+    Wordle,
+    Sutom,
+}
+
+impl Palette {
+    fn class(self, pattern: Pattern) -> &'static str {
+        match (self, pattern) {
+            (Palette::Wordle, Pattern::Correct) => "bg-success",
+            (_, Pattern::Misplaced) => "bg-warning",
+            (Palette::Wordle, Pattern::Incorrect) => "bg-secondary",
+            (Palette::Sutom, Pattern::Correct) => "bg-danger",
+            (Palette::Sutom, Pattern::Incorrect) => "bg-primary",
+        }
+    }
+}
+
+/// A row of the board not guessed yet.
+#[component]
+fn EmptyTiles(length: usize) -> Element {
+    rsx! {
+        div { class:"d-flex gap-1 mb-1",
+            for _ in 0..length {
+                span { class:"badge fs-4 bg-light border", "\u{a0}" }
+            }
+        }
+    }
+}
+
+
+/// Clicking a header sorts every word by that column, best first, after calling `on_sort`.
+#[component]
+fn WordleTable(words: Vec<WordleEntity>, size : usize, #[props(default)] on_sort: EventHandler) -> Element {
+    let mut sorted_by = use_signal(|| None::<Column>);
+    let mut words = words;
+    if let Some(column) = sorted_by() {
+        column.sort(&mut words);
+    }
+    rsx! {
+        table { class :"table", thead {
+        tr {
+            for column in Column::ALL {
+                th { style:"cursor:pointer", onclick: move |_| { on_sort.call(()); sorted_by.set(Some(column)) },
+                    if sorted_by() == Some(column) { "{column.title()} ▾" } else { "{column.title()}" }
+                }
+            }
+        }
+        {
+            words.iter().take(size).map(|we| {
+                rsx!{
+                    tr {
+                        for column in Column::ALL {
+                            td {{column.value(we)}}
+                        }
                     }
                 }
             })
         }}}
     }
 }
+
+#[derive(PartialEq, Clone, Copy)]
+enum Column {
+    Word,
+    Frequency,
+    Probability,
+    Entropy,
+    TwoStepEntropy,
+    TwoStepRemaining,
+    ExpectedGuesses,
+    LargestBucket,
+    ExpectedBucket,
+}
+
+impl Column {
+    const ALL: [Column; 9] = [Column::Word, Column::Frequency, Column::Probability, Column::Entropy, Column::TwoStepEntropy,
+        Column::TwoStepRemaining, Column::ExpectedGuesses, Column::LargestBucket, Column::ExpectedBucket];
+
+    fn title(self) -> &'static str {
+        match self {
+            Column::Word => "Word",
+            Column::Frequency => "Frequency",
+            Column::Probability => "Probability",
+            Column::Entropy => "Entropy",
+            Column::TwoStepEntropy => "Two-step entropy",
+            Column::TwoStepRemaining => "Two-step remaining",
+            Column::ExpectedGuesses => "Expected guesses",
+            Column::LargestBucket => "Largest bucket",
+            Column::ExpectedBucket => "Expected bucket",
+        }
+    }
+
+    fn value(self, we: &WordleEntity) -> String {
+        match self {
+            Column::Word => we.word.clone(),
+            Column::Frequency => we.frequency.to_string(),
+            Column::Probability => we.probability.to_string(),
+            Column::Entropy => we.entropy.to_string(),
+            Column::TwoStepEntropy => format_score(we.two_step_entropy),
+            Column::TwoStepRemaining => format_score(we.two_step_remaining),
+            Column::ExpectedGuesses => we.expected_guesses.to_string(),
+            Column::LargestBucket => we.largest_bucket.to_string(),
+            Column::ExpectedBucket => we.expected_bucket.to_string(),
+        }
+    }
+
+    fn sort(self, words: &mut [WordleEntity]) {
+        match self {
+            Column::Word => words.sort_by(|a, b| a.word.cmp(&b.word)),
+            Column::Frequency => words.sort_by(|a, b| b.frequency.total_cmp(&a.frequency)),
+            Column::Probability => words.sort_by(|a, b| b.probability.total_cmp(&a.probability)),
+            Column::Entropy => ScoringMode::Entropy.sort(words),
+            Column::TwoStepEntropy => ScoringMode::TwoStep.sort(words),
+            // Words without a two-step score go last.
+            Column::TwoStepRemaining => words.sort_by(|a, b| a.two_step_remaining.unwrap_or(f32::INFINITY)
+                .total_cmp(&b.two_step_remaining.unwrap_or(f32::INFINITY))),
+            Column::ExpectedGuesses => ScoringMode::ExpectedGuesses.sort(words),
+            Column::LargestBucket => ScoringMode::Minimax.sort(words),
+            Column::ExpectedBucket => ScoringMode::ExpectedBucket.sort(words),
+        }
+    }
+}
+
+fn format_score(score: Option<f32>) -> String {
+    score.map_or_else(String::new, |score| score.to_string())
+}